@@ -0,0 +1,56 @@
+//! Helpers to choose the I2S clock source frequency.
+//!
+//! This library doesn't control the RCC peripheral, so the I2S clock source (usually a PLL) must
+//! be configured by the MCU HAL or the user. Functions of this module are pure computations to
+//! help choosing it.
+use crate::driver::_coef;
+use crate::marker::{DataFormat, I2sStandard};
+
+/// Smallest division factor of the prescaler, that is `(2 * div) + odd` with `div = 2` and
+/// `odd = false`.
+const MIN_DIVISION: u32 = 4;
+
+/// Get an I2S clock source frequency allowing to reach exactly `sample_rate`.
+///
+/// `master_clock` tells whether master clock is enabled, and markers give the standard and data
+/// format that will be used. The returned frequency is the smallest suitable one, reached with a
+/// prescaler division factor of 4. More generally, if `f` is the returned frequency, `f * n / 4`
+/// is suitable for any integer `n` from 4 to 511 when the result is an integer.
+///
+/// ```
+/// # use stm32_i2s_v12x::clock::suggest_i2s_clock;
+/// # use stm32_i2s_v12x::marker::*;
+/// // 48 kHz with master clock: 256 * 48_000 * 4
+/// assert_eq!(
+///     suggest_i2s_clock(48_000, true, Philips, Data16Channel32),
+///     49_152_000
+/// );
+/// ```
+pub fn suggest_i2s_clock<STD, FMT>(
+    sample_rate: u32,
+    master_clock: bool,
+    _standard: STD,
+    _format: FMT,
+) -> u32
+where
+    STD: I2sStandard,
+    FMT: DataFormat,
+{
+    _coef(master_clock, STD::VALUE, FMT::VALUE) * MIN_DIVISION * sample_rate
+}
+
+/// Alias of [`suggest_i2s_clock`], the lowest I2S clock source frequency that can produce
+/// `sample_rate`.
+///
+/// The prescaler can't divide by less than 4, so a lower frequency can't reach the requested
+/// rate, whatever the prescaler setting. This name is useful to choose the slowest adequate PLL
+/// setting on low power applications. Higher frequencies are usable up to a division factor of
+/// 511.
+///
+/// ```
+/// # use stm32_i2s_v12x::clock::min_clock_for;
+/// # use stm32_i2s_v12x::marker::*;
+/// // 8 kHz, without master clock, 16 bit channels: 32 * 8_000 * 4
+/// assert_eq!(min_clock_for(8_000, false, Philips, Data16Channel16), 1_024_000);
+/// ```
+pub use self::suggest_i2s_clock as min_clock_for;
@@ -0,0 +1,98 @@
+//! Ready made transfer configurations for some common audio codecs.
+//!
+//! Each function returns a master configuration: the STM32 generates the bit clock, the WS signal
+//! and the master clock, and the codec is slave. The returned configuration requires the exact
+//! sampling frequency, and `None` is returned when the codec doesn't support `sample_rate` with
+//! this setup, or when `i2s_freq` doesn't allow to reach it exactly. Requirements of each codec
+//! are documented on its function, and codec registers or mode pins, if any, are still to be
+//! set by the user.
+//!
+//! The STM32 master clock is 256 times the sampling frequency, so `i2s_freq` must be a multiple
+//! of `256 * sample_rate`, see [`suggest_i2s_clock`](crate::clock::suggest_i2s_clock).
+//!
+//! ```
+//! # use stm32_i2s_v12x::codecs;
+//! assert!(codecs::wm8731_slave(49_152_000, 48_000).is_some());
+//! // 32 kHz isn't reachable with a 256 * fs master clock on the WM8731
+//! assert!(codecs::wm8731_slave(49_152_000, 32_000).is_none());
+//! // 44.1 kHz can't be reached exactly from this clock
+//! assert!(codecs::cs4344(49_152_000, 44_100).is_none());
+//! ```
+use crate::driver::{DataFormat, I2sDriverConfig};
+use crate::marker::{Data16Channel32, Data32Channel32, Master, Philips, Receive, Transmit};
+use crate::transfer::I2sTransferConfig;
+
+/// Return `true` if `sample_rate` can be reached exactly with master clock enabled.
+fn reachable(i2s_freq: u32, sample_rate: u32, data_format: DataFormat) -> bool {
+    I2sDriverConfig::new_master()
+        .data_format(data_format)
+        .master_clock(true)
+        .require_frequency(sample_rate)
+        .diagnose(i2s_freq)
+        .is_ok()
+}
+
+/// Configuration for a Wolfson/Cirrus WM8731 codec in slave mode, transmitting to its DAC.
+///
+/// The codec must be configured for the I2S format with 16 bit input word length. In normal
+/// mode, a 256 * fs master clock allows 44.1 kHz and 48 kHz, and 88.2 kHz and 96 kHz with the
+/// core clock divided by 2 (CLKIDIV2). Sampling control bits must match the chosen rate. Use
+/// `receive()` on the returned configuration to read from the ADC instead.
+pub fn wm8731_slave(
+    i2s_freq: u32,
+    sample_rate: u32,
+) -> Option<I2sTransferConfig<Master, Transmit, Philips, Data16Channel32>> {
+    const RATES: [u32; 4] = [44_100, 48_000, 88_200, 96_000];
+    if !RATES.contains(&sample_rate)
+        || !reachable(i2s_freq, sample_rate, DataFormat::Data16Channel32)
+    {
+        return None;
+    }
+    let config = I2sTransferConfig::new_master()
+        .data_format(Data16Channel32)
+        .master_clock(true)
+        .require_frequency(sample_rate);
+    Some(config)
+}
+
+/// Configuration for a TI PCM1808 ADC in slave mode.
+///
+/// The FMT pin must be low to select the I2S format, and the mode pins must select slave mode.
+/// The system clock is 256 * fs, and the sampling frequency must be from 8 kHz to 96 kHz. The
+/// codec outputs 24 bit data, received in the upper bits of `i32` samples.
+pub fn pcm1808(
+    i2s_freq: u32,
+    sample_rate: u32,
+) -> Option<I2sTransferConfig<Master, Receive, Philips, Data32Channel32>> {
+    if !(8_000..=96_000).contains(&sample_rate)
+        || !reachable(i2s_freq, sample_rate, DataFormat::Data32Channel32)
+    {
+        return None;
+    }
+    let config = I2sTransferConfig::new_master()
+        .receive()
+        .data_format(Data32Channel32)
+        .master_clock(true)
+        .require_frequency(sample_rate);
+    Some(config)
+}
+
+/// Configuration for a Cirrus CS4344 DAC.
+///
+/// The CS4344 uses the I2S format and detects its clock ratios automatically. A 256 * fs master
+/// clock is used in single speed mode, so the sampling frequency must be from 4 kHz to 50 kHz.
+pub fn cs4344(
+    i2s_freq: u32,
+    sample_rate: u32,
+) -> Option<I2sTransferConfig<Master, Transmit, Philips, Data16Channel32>> {
+    if !(4_000..=50_000).contains(&sample_rate)
+        || !reachable(i2s_freq, sample_rate, DataFormat::Data16Channel32)
+    {
+        return None;
+    }
+    let config = I2sTransferConfig::new_master()
+        .data_format(Data16Channel32)
+        .master_clock(true)
+        .require_frequency(sample_rate);
+    Some(config)
+}
@@ -0,0 +1,78 @@
+//! Conversions between 16 bit and 32 bit audio frames.
+//!
+//! This helps to bridge parts of an application using different data formats, for example a
+//! 16 bit capture and a 32 bit playback. Since Rust doesn't allow `From` implementations between
+//! tuples defined elsewhere, conversions are done with the [`widen`] and [`narrow`] functions.
+//!
+//! Samples are full scale values: widening moves a 16 bit sample to the 16 most significant bits
+//! of a 32 bit sample, and narrowing keeps the 16 most significant bits, so the signal level is
+//! preserved.
+//!
+//! ```
+//! # use stm32_i2s_v12x::convert::{narrow, widen};
+//! assert_eq!(widen((0x1234i16, -1i16)), (0x1234_0000i32, -0x1_0000i32));
+//! assert_eq!(narrow((0x1234_5678i32, i32::MIN)), (0x1234i16, i16::MIN));
+//! assert_eq!(narrow(widen(-42i16)), -42);
+//! ```
+
+/// Audio frames made of 16 bit samples, that can be converted to 32 bit samples.
+///
+/// This is implemented for `i16` and `(i16, i16)`.
+pub trait Widen: Copy {
+    /// The same frame with 32 bit samples.
+    type Wide;
+    /// Convert to 32 bit samples, see [`widen`].
+    fn widen(self) -> Self::Wide;
+}
+
+/// Audio frames made of 32 bit samples, that can be converted to 16 bit samples.
+///
+/// This is implemented for `i32` and `(i32, i32)`.
+pub trait Narrow: Copy {
+    /// The same frame with 16 bit samples.
+    type Narrow;
+    /// Convert to 16 bit samples, see [`narrow`].
+    fn narrow(self) -> Self::Narrow;
+}
+
+impl Widen for i16 {
+    type Wide = i32;
+    fn widen(self) -> i32 {
+        (self as i32) << 16
+    }
+}
+
+impl<T: Widen> Widen for (T, T) {
+    type Wide = (T::Wide, T::Wide);
+    fn widen(self) -> Self::Wide {
+        (self.0.widen(), self.1.widen())
+    }
+}
+
+impl Narrow for i32 {
+    type Narrow = i16;
+    fn narrow(self) -> i16 {
+        (self >> 16) as i16
+    }
+}
+
+impl<T: Narrow> Narrow for (T, T) {
+    type Narrow = (T::Narrow, T::Narrow);
+    fn narrow(self) -> Self::Narrow {
+        (self.0.narrow(), self.1.narrow())
+    }
+}
+
+/// Convert a frame of 16 bit samples to 32 bit samples, by moving samples to the most
+/// significant bits. This is lossless.
+pub fn widen<T: Widen>(frame: T) -> T::Wide {
+    frame.widen()
+}
+
+/// Convert a frame of 32 bit samples to 16 bit samples, by keeping the most significant bits.
+///
+/// This is lossy: the 16 least significant bits are dropped without rounding nor dithering, which
+/// rounds samples towards negative infinity.
+pub fn narrow<T: Narrow>(frame: T) -> T::Narrow {
+    frame.narrow()
+}
@@ -88,7 +88,8 @@
 use core::marker::PhantomData;
 
 use crate::pac::spi1::RegisterBlock;
-use crate::pac::spi1::{i2spr, sr};
+use crate::pac::spi1::{i2scfgr, i2spr, sr};
+use crate::transfer::{FrameFormat, FromRawFrame, I2sTransferError, ToRawFrame};
 use crate::{DualI2sPeripheral, I2sPeripheral, WsPin};
 
 pub use crate::marker::{self, *};
@@ -119,6 +120,11 @@ impl<MS, DIR, STD> Status<MS, DIR, STD> {
     pub fn bsy(&self) -> bool {
         self.value.bsy().bit()
     }
+
+    /// Get the raw CHSIDE bit, whatever the standard.
+    pub(crate) fn chside_bit(&self) -> bool {
+        self.value.chside().bit()
+    }
 }
 
 impl<MS, DIR, STD> Status<MS, DIR, STD>
@@ -197,14 +203,14 @@ impl<STD> Status<Slave, Transmit, STD> {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum SlaveOrMaster {
     Slave,
     Master,
 }
 
 /// Various ways to specify sampling frequency.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Frequency {
     Prescaler(bool, u8),
     Request(u32),
@@ -213,37 +219,40 @@ enum Frequency {
 
 /// Those thing are not part of the public API but appear on public trait or trait bound.
 pub(crate) mod private {
-    #[derive(Debug, Clone, Copy)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub enum TransmitOrReceive {
         Transmit,
         Receive,
     }
 
-    #[derive(Debug, Clone, Copy, Eq, PartialEq)]
-    /// I2s standard selection.
-    pub enum I2sStandard {
-        /// Philips I2S
-        Philips,
-        /// MSB Justified
-        Msb,
-        /// LSB Justified
-        Lsb,
-        /// PCM with short frame synchronisation.
-        PcmShortSync,
-        /// PCM with long frame synchronisation.
-        PcmLongSync,
-    }
-
     /// This trait allow to have generic code for I2sCore.
     use super::RegisterBlock;
     pub trait I2sCoreRegisters {
         fn registers(&self) -> &RegisterBlock;
     }
 }
-pub(crate) use private::{I2sCoreRegisters, I2sStandard, TransmitOrReceive};
+pub(crate) use private::{I2sCoreRegisters, TransmitOrReceive};
+
+/// I2s standard selection.
+///
+/// This is the runtime counterpart of the standard markers, see
+/// [`I2sStandard::VALUE`](marker::I2sStandard::VALUE).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum I2sStandard {
+    /// Philips I2S
+    Philips,
+    /// MSB Justified
+    Msb,
+    /// LSB Justified
+    Lsb,
+    /// PCM with short frame synchronisation.
+    PcmShortSync,
+    /// PCM with long frame synchronisation.
+    PcmLongSync,
+}
 
 /// Steady state clock polarity
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ClockPolarity {
     /// Clock low when idle
     IdleLow,
@@ -251,8 +260,27 @@ pub enum ClockPolarity {
     IdleHigh,
 }
 
+/// Frame synchronisation of PCM standards, see [`I2sDriver::pcm_sync`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PcmSync {
+    /// Synchronisation pulse one bit clock long, like `PcmShortSync`
+    Short,
+    /// Synchronisation pulse 13 bit clocks long, like `PcmLongSync`
+    Long,
+}
+
+/// Ways to disable the I2S peripheral, see [`I2sDriver::disable_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DisableMode {
+    /// Disable the peripheral immediately.
+    #[default]
+    Immediate,
+    /// Wait for the BSY flag to be cleared before disabling the peripheral.
+    WhenIdle,
+}
+
 /// Data length to be transferred and channel length
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DataFormat {
     /// 16 bit data length on 16 bit wide channel
     Data16Channel16,
@@ -270,7 +298,61 @@ impl Default for DataFormat {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Issues of a master configuration, returned by [`I2sDriverConfig::diagnose`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigDiagnostics {
+    /// Requested or required sampling frequency, `None` when the prescaler is set directly.
+    pub requested_frequency: Option<u32>,
+    /// Sampling frequency that the driver would effectively use.
+    pub effective_frequency: u32,
+    /// `true` when the requested frequency needs a prescaler division factor out of the 4 to 511
+    /// range, so the factor is saturated. This is also the case for a zero frequency or a
+    /// frequency so high that computing the division overflows.
+    pub division_out_of_range: bool,
+    /// `true` when an exact frequency is required and can't be reached. Instantiating the driver
+    /// with this configuration would panic.
+    pub required_frequency_unreachable: bool,
+}
+
+impl ConfigDiagnostics {
+    /// Get the difference between the effective and the requested frequency, if any.
+    pub fn frequency_deviation(&self) -> Option<i64> {
+        self.requested_frequency
+            .map(|freq| self.effective_frequency as i64 - freq as i64)
+    }
+
+    /// Return `true` if no issue was detected, including any frequency deviation.
+    pub fn is_ok(&self) -> bool {
+        !self.division_out_of_range
+            && !self.required_frequency_unreachable
+            && self.frequency_deviation().unwrap_or(0) == 0
+    }
+}
+
+/// Raw content of the SPI registers, returned by [`I2sDriver::register_dump`].
+///
+/// This is meant for post-mortem debugging, e.g. to be logged when a fault occurs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterDump {
+    /// Content of CR1
+    pub cr1: u16,
+    /// Content of CR2
+    pub cr2: u16,
+    /// Content of SR, `None` if it wasn't read
+    pub sr: Option<u16>,
+    /// Content of CRCPR
+    pub crcpr: u16,
+    /// Content of RXCRCR
+    pub rxcrcr: u16,
+    /// Content of TXCRCR
+    pub txcrcr: u16,
+    /// Content of I2SCFGR
+    pub i2scfgr: u16,
+    /// Content of I2SPR
+    pub i2spr: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// I2S driver configuration
 ///
 /// This can be used as an i2s driver builder.
@@ -289,6 +371,7 @@ pub struct I2sDriverConfig<MS, DIR, STD> {
     data_format: DataFormat,
     master_clock: bool,
     frequency: Frequency,
+    assumed_clock: Option<u32>,
 
     _ms: PhantomData<MS>,
     _tr: PhantomData<DIR>,
@@ -306,6 +389,7 @@ impl I2sDriverConfig<Slave, Transmit, Philips> {
             data_format: Default::default(),
             master_clock: false,
             frequency: Frequency::Prescaler(false, 0b10),
+            assumed_clock: None,
             _ms: PhantomData,
             _tr: PhantomData,
             _std: PhantomData,
@@ -324,6 +408,7 @@ impl I2sDriverConfig<Master, Transmit, Philips> {
             data_format: Default::default(),
             master_clock: false,
             frequency: Frequency::Prescaler(false, 0b10),
+            assumed_clock: None,
             _ms: PhantomData,
             _tr: PhantomData,
             _std: PhantomData,
@@ -336,6 +421,51 @@ fn div_round(n: u32, d: u32) -> u32 {
     (n + (d >> 1)) / d
 }
 
+fn _set_standard(w: &mut i2scfgr::W, standard: I2sStandard) {
+    match standard {
+        I2sStandard::Philips => w.i2sstd().philips(),
+        I2sStandard::Msb => w.i2sstd().msb(),
+        I2sStandard::Lsb => w.i2sstd().lsb(),
+        I2sStandard::PcmShortSync => w.i2sstd().pcm().pcmsync().short(),
+        I2sStandard::PcmLongSync => w.i2sstd().pcm().pcmsync().long(),
+    };
+}
+
+fn _set_clock_polarity(w: &mut i2scfgr::W, polarity: ClockPolarity) {
+    match polarity {
+        ClockPolarity::IdleLow => w.ckpol().idle_low(),
+        ClockPolarity::IdleHigh => w.ckpol().idle_high(),
+    };
+}
+
+fn _clock_polarity(registers: &RegisterBlock) -> ClockPolarity {
+    if registers.i2scfgr.read().ckpol().is_idle_high() {
+        ClockPolarity::IdleHigh
+    } else {
+        ClockPolarity::IdleLow
+    }
+}
+
+fn _pcm_sync(registers: &RegisterBlock) -> Option<PcmSync> {
+    let i2scfgr = registers.i2scfgr.read();
+    if !i2scfgr.i2sstd().is_pcm() {
+        None
+    } else if i2scfgr.pcmsync().is_long() {
+        Some(PcmSync::Long)
+    } else {
+        Some(PcmSync::Short)
+    }
+}
+
+fn _set_data_format(w: &mut i2scfgr::W, data_format: DataFormat) {
+    match data_format {
+        DataFormat::Data16Channel16 => w.datlen().sixteen_bit().chlen().sixteen_bit(),
+        DataFormat::Data16Channel32 => w.datlen().sixteen_bit().chlen().thirty_two_bit(),
+        DataFormat::Data24Channel32 => w.datlen().twenty_four_bit().chlen().thirty_two_bit(),
+        DataFormat::Data32Channel32 => w.datlen().thirty_two_bit().chlen().thirty_two_bit(),
+    };
+}
+
 // unsafe, div should be greater or equal to 2
 fn _set_prescaler(w: &mut i2spr::W, odd: bool, div: u8) {
     w.odd().bit(odd);
@@ -391,9 +521,7 @@ fn _set_require_frequency(
     data_format: DataFormat,
 ) {
     let coef = _coef(mclk, std, data_format);
-    let division = i2s_clock / (coef * request_freq);
-    let rem = i2s_clock % (coef * request_freq);
-    if rem == 0 && division >= 4 && division <= 511 {
+    if let Some(division) = _exact_division(i2s_clock, request_freq, coef) {
         let odd = (division & 1) == 1;
         let div = (division >> 1) as u8;
         _set_prescaler(w, odd, div);
@@ -402,9 +530,17 @@ fn _set_require_frequency(
     };
 }
 
+// division factor giving exactly `freq`, if it exists and is in the prescaler range
+fn _exact_division(i2s_clock: u32, freq: u32, coef: u32) -> Option<u32> {
+    let divisor = coef.checked_mul(freq).filter(|&divisor| divisor != 0)?;
+    let division = i2s_clock / divisor;
+    let rem = i2s_clock % divisor;
+    (rem == 0 && (4..=511).contains(&division)).then_some(division)
+}
+
 // see _set_request_frequency for explanation
-fn _coef(mclk: bool, std: I2sStandard, data_format: DataFormat) -> u32 {
-    use I2sStandard::*;
+pub(crate) fn _coef(mclk: bool, std: I2sStandard, data_format: DataFormat) -> u32 {
+    use self::I2sStandard::*;
     let nb_chan = match std {
         Philips | Msb | Lsb => 2,
         PcmShortSync | PcmLongSync => 1,
@@ -436,6 +572,15 @@ fn _sample_rate(registers: &RegisterBlock, i2s_freq: u32) -> u32 {
     }
 }
 
+// ratio between master clock and sample rate, see _set_request_frequency for explanation
+fn _master_clock_ratio(registers: &RegisterBlock) -> u32 {
+    if registers.i2scfgr.read().i2sstd().is_pcm() {
+        128
+    } else {
+        256
+    }
+}
+
 impl<MS, DIR, STD> I2sDriverConfig<MS, DIR, STD> {
     /// Instantiate the driver by wrapping the given [`I2sPeripheral`].
     ///
@@ -449,6 +594,12 @@ impl<MS, DIR, STD> I2sDriverConfig<MS, DIR, STD> {
             _tr: PhantomData,
             _std: PhantomData,
         };
+        self._write_registers(&driver);
+        driver
+    }
+
+    /// Write the configuration to the registers of `driver`, as done when instantiating it.
+    fn _write_registers<I: I2sPeripheral>(&self, driver: &I2sDriver<I, MS, DIR, STD>) {
         driver.registers().cr1.reset(); // ensure SPI is disabled
         driver.registers().cr2.reset(); // disable interrupt and DMA request
         driver.registers().i2scfgr.write(|w| {
@@ -459,30 +610,22 @@ impl<MS, DIR, STD> I2sDriverConfig<MS, DIR, STD> {
                 (SlaveOrMaster::Master, TransmitOrReceive::Transmit) => w.i2scfg().master_tx(),
                 (SlaveOrMaster::Master, TransmitOrReceive::Receive) => w.i2scfg().master_rx(),
             };
-            match self.standard {
-                I2sStandard::Philips => w.i2sstd().philips(),
-                I2sStandard::Msb => w.i2sstd().msb(),
-                I2sStandard::Lsb => w.i2sstd().lsb(),
-                I2sStandard::PcmShortSync => w.i2sstd().pcm().pcmsync().short(),
-                I2sStandard::PcmLongSync => w.i2sstd().pcm().pcmsync().long(),
-            };
-            match self.data_format {
-                DataFormat::Data16Channel16 => w.datlen().sixteen_bit().chlen().sixteen_bit(),
-                DataFormat::Data16Channel32 => w.datlen().sixteen_bit().chlen().thirty_two_bit(),
-                DataFormat::Data24Channel32 => {
-                    w.datlen().twenty_four_bit().chlen().thirty_two_bit()
-                }
-                DataFormat::Data32Channel32 => w.datlen().thirty_two_bit().chlen().thirty_two_bit(),
-            };
+            _set_standard(w, self.standard);
+            _set_clock_polarity(w, self.clock_polarity);
+            _set_data_format(w, self.data_format);
             w
         });
+        let i2s_freq = || {
+            self.assumed_clock
+                .unwrap_or_else(|| driver.i2s_peripheral.i2s_freq())
+        };
         driver.registers().i2spr.write(|w| {
             w.mckoe().bit(self.master_clock);
             match self.frequency {
                 Frequency::Prescaler(odd, div) => _set_prescaler(w, odd, div),
                 Frequency::Request(freq) => _set_request_frequency(
                     w,
-                    driver.i2s_peripheral.i2s_freq(),
+                    i2s_freq(),
                     freq,
                     self.master_clock,
                     self.standard,
@@ -490,7 +633,7 @@ impl<MS, DIR, STD> I2sDriverConfig<MS, DIR, STD> {
                 ),
                 Frequency::Require(freq) => _set_require_frequency(
                     w,
-                    driver.i2s_peripheral.i2s_freq(),
+                    i2s_freq(),
                     freq,
                     self.master_clock,
                     self.standard,
@@ -499,7 +642,6 @@ impl<MS, DIR, STD> I2sDriverConfig<MS, DIR, STD> {
             }
             w
         });
-        driver
     }
 }
 
@@ -523,6 +665,7 @@ impl<MS, DIR, STD> I2sDriverConfig<MS, DIR, STD> {
             data_format: self.data_format,
             master_clock: self.master_clock,
             frequency: self.frequency,
+            assumed_clock: self.assumed_clock,
             _ms: PhantomData,
             _tr: PhantomData,
             _std: PhantomData,
@@ -538,6 +681,7 @@ impl<MS, DIR, STD> I2sDriverConfig<MS, DIR, STD> {
             data_format: self.data_format,
             master_clock: self.master_clock,
             frequency: self.frequency,
+            assumed_clock: self.assumed_clock,
             _ms: PhantomData,
             _tr: PhantomData,
             _std: PhantomData,
@@ -557,6 +701,7 @@ impl<MS, DIR, STD> I2sDriverConfig<MS, DIR, STD> {
             data_format: self.data_format,
             master_clock: self.master_clock,
             frequency: self.frequency,
+            assumed_clock: self.assumed_clock,
             _ms: PhantomData,
             _tr: PhantomData,
             _std: PhantomData,
@@ -578,11 +723,38 @@ impl<MS, DIR, STD> I2sDriverConfig<MS, DIR, STD> {
             data_format: self.data_format,
             master_clock: self.master_clock,
             frequency: self.frequency,
+            assumed_clock: self.assumed_clock,
             _ms: PhantomData,
             _tr: PhantomData,
             _std: PhantomData,
         }
     }
+    /// Select the I2s standard from raw I2SSTD and PCMSYNC values, without changing the `STD`
+    /// typestate.
+    ///
+    /// This is an escape hatch for hardware that doesn't match the standard given by the
+    /// typestate, and bypasses its guarantees: the register setting is used for frequency
+    /// computations, but everything relying on `STD` still assumes the marker standard. This
+    /// includes the WS start level used by slave transfers to synchronise, the frame layout of
+    /// `I2sTransfer`, the channel flag of non PCM standards and the sampling rate adjustment of
+    /// `change_standard`. Note the five standards cover all valid register combinations,
+    /// `pcmsync` being ignored when `i2sstd_bits` doesn't select PCM, so a mixed framing like MSB
+    /// justified data with the Philips WS polarity can't be configured.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `i2sstd_bits` is not in the `0..=3` range of the I2SSTD field.
+    pub fn raw_standard(mut self, i2sstd_bits: u8, pcmsync: bool) -> Self {
+        assert!(i2sstd_bits <= 0b11, "Invalid I2SSTD value");
+        self.standard = match (i2sstd_bits, pcmsync) {
+            (0b00, _) => I2sStandard::Philips,
+            (0b01, _) => I2sStandard::Msb,
+            (0b10, _) => I2sStandard::Lsb,
+            (_, false) => I2sStandard::PcmShortSync,
+            (_, true) => I2sStandard::PcmLongSync,
+        };
+        self
+    }
     /// Select steady state clock polarity
     // datasheet don't precise how it affect I2s operation. In particular, this may meaningless for
     // slave operation.
@@ -616,6 +788,7 @@ impl<MS, DIR, STD> I2sDriverConfig<MS, DIR, STD> {
             data_format,
             master_clock: false,
             frequency: Frequency::Prescaler(false, 0b10),
+            assumed_clock: None,
             _ms: PhantomData,
             _tr: PhantomData,
             _std: PhantomData,
@@ -631,6 +804,7 @@ impl<MS, DIR, STD> I2sDriverConfig<MS, DIR, STD> {
             data_format,
             master_clock,
             frequency,
+            assumed_clock,
             ..
         } = self;
         I2sDriverConfig::<Master, DIR, STD> {
@@ -641,6 +815,7 @@ impl<MS, DIR, STD> I2sDriverConfig<MS, DIR, STD> {
             data_format,
             master_clock,
             frequency,
+            assumed_clock,
             _ms: PhantomData,
             _tr: PhantomData,
             _std: PhantomData,
@@ -686,6 +861,24 @@ impl<DIR, STD> I2sDriverConfig<Master, DIR, STD> {
         self
     }
 
+    /// Set the total division factor of the prescaler, that is `(2 * div) + odd`, instead of
+    /// giving `odd` and `div` separately.
+    ///
+    /// This setting only has meaning and can be only set for master. No frequency computation is
+    /// done: the sample rate is the I2S clock frequency divided by `divisor` and by a coefficient
+    /// depending on the standard, data format and master clock, see
+    /// [`clock::suggest_i2s_clock`](crate::clock::suggest_i2s_clock).
+    ///
+    /// # Panics
+    ///
+    /// `divisor` must be in the range `[4, 511]`, otherwise this function panics.
+    pub fn divide_clock(self, divisor: u16) -> Self {
+        if !(4..=511).contains(&divisor) {
+            panic!("divisor is out of the [4, 511] range")
+        }
+        self.prescaler(divisor & 1 == 1, (divisor >> 1) as u8)
+    }
+
     /// Request an audio sampling frequency.
     ///
     /// The actual audio sampling frequency may be different.
@@ -701,6 +894,171 @@ impl<DIR, STD> I2sDriverConfig<Master, DIR, STD> {
         self.frequency = Frequency::Require(freq);
         self
     }
+
+    /// Use `freq` as I2S clock source frequency when computing the prescaler of a requested or
+    /// required frequency, instead of asking the peripheral.
+    ///
+    /// This is meant for HALs unable to report the I2S clock frequency, since
+    /// [`I2sPeripheral::i2s_freq`] is allowed to panic. An incorrect value gives a wrong sampling
+    /// rate. Driver methods like `sample_rate` still ask the peripheral.
+    pub fn with_assumed_clock(mut self, freq: u32) -> Self {
+        self.assumed_clock = Some(freq);
+        self
+    }
+
+    /// Instantiate the driver like [`i2s_driver`](Self::i2s_driver), and return it along with
+    /// its actual sample rate, eg to log it at startup.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if an exact frequency is required and that frequency cannot be set.
+    pub fn build_and_report<I: I2sPeripheral>(
+        self,
+        i2s_peripheral: I,
+    ) -> (I2sDriver<I, Master, DIR, STD>, u32) {
+        let driver = self.i2s_driver(i2s_peripheral);
+        let sample_rate = driver.sample_rate();
+        (driver, sample_rate)
+    }
+
+    /// Return `true` if `rate` can be reached exactly from the I2S clock source frequency
+    /// `i2s_freq` with the current standard, data format and master clock settings, meaning
+    /// `require_frequency(rate)` wouldn't panic.
+    pub fn is_rate_exact(&self, i2s_freq: u32, rate: u32) -> bool {
+        let coef = _coef(self.master_clock, self.standard, self.data_format);
+        _exact_division(i2s_freq, rate, coef).is_some()
+    }
+
+    /// Check the frequency settings against the given I2S clock source frequency.
+    ///
+    /// Unlike driver instantiation, this never panics and reports every detected issue. This uses
+    /// the same computation as the driver.
+    pub fn diagnose(&self, i2s_freq: u32) -> ConfigDiagnostics {
+        let coef = _coef(self.master_clock, self.standard, self.data_format);
+        let mut diagnostics = ConfigDiagnostics {
+            requested_frequency: None,
+            effective_frequency: 0,
+            division_out_of_range: false,
+            required_frequency_unreachable: false,
+        };
+        let division = match self.frequency {
+            Frequency::Prescaler(odd, div) => (2 * div as u32) + odd as u32,
+            Frequency::Request(freq) | Frequency::Require(freq) => {
+                diagnostics.requested_frequency = Some(freq);
+                if let Frequency::Require(_) = self.frequency {
+                    diagnostics.required_frequency_unreachable =
+                        _exact_division(i2s_freq, freq, coef).is_none();
+                }
+                let division = match coef.checked_mul(freq) {
+                    Some(0) => u32::MAX,
+                    Some(divisor) => div_round(i2s_freq, divisor),
+                    None => 0,
+                };
+                diagnostics.division_out_of_range = !(4..=511).contains(&division);
+                division.clamp(4, 511)
+            }
+        };
+        diagnostics.effective_frequency = i2s_freq / (coef * division);
+        diagnostics
+    }
+
+    /// For each rate of `rates`, compute the sampling rate that would be effectively used if it
+    /// was requested, and its error in ppm.
+    ///
+    /// Results are written in `out`, in the same order as `rates`, as `(effective_rate, ppm)`
+    /// pairs. Only `min(rates.len(), out.len())` rates are processed. A rate of 0 gives `(0, 0)`, and
+    /// the error saturates at the bounds of `i32`. A rate so high that the computation overflows is
+    /// unreachable and gives an effective rate of 0, that is an error of -1000000 ppm.
+    /// This uses the same computation as [`request_frequency`](Self::request_frequency).
+    pub fn rate_table(&self, i2s_freq: u32, rates: &[u32], out: &mut [(u32, i32)]) {
+        let coef = _coef(self.master_clock, self.standard, self.data_format);
+        for (&rate, result) in rates.iter().zip(out.iter_mut()) {
+            if rate == 0 {
+                *result = (0, 0);
+                continue;
+            }
+            let effective = match coef.checked_mul(rate) {
+                Some(divisor) => i2s_freq / (coef * div_round(i2s_freq, divisor).clamp(4, 511)),
+                None => 0,
+            };
+            let ppm = (effective as i64 - rate as i64) * 1_000_000 / rate as i64;
+            *result = (
+                effective,
+                ppm.clamp(i32::MIN as i64, i32::MAX as i64) as i32,
+            );
+        }
+    }
+}
+
+/// Two driver configurations sharing the same typestate, see [`I2sDriver::apply_bank`].
+///
+/// This is meant for firmware switching between two known configurations, eg 48 kHz playback and
+/// 16 kHz voice, without storing them at each call site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConfigBank<MS, DIR, STD> {
+    configs: [I2sDriverConfig<MS, DIR, STD>; 2],
+}
+
+impl<MS, DIR, STD> ConfigBank<MS, DIR, STD> {
+    /// Create a bank holding `first` at index 0 and `second` at index 1.
+    pub fn new(
+        first: I2sDriverConfig<MS, DIR, STD>,
+        second: I2sDriverConfig<MS, DIR, STD>,
+    ) -> Self {
+        Self {
+            configs: [first, second],
+        }
+    }
+
+    /// Get the configuration at index `which`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `which` is not 0 or 1.
+    pub fn config(&self, which: usize) -> &I2sDriverConfig<MS, DIR, STD> {
+        &self.configs[which]
+    }
+}
+
+/// Software helper to track frame boundaries in interrupt handlers.
+///
+/// TXE and RXNE events happen once per half word, so an interrupt handler runs several times per
+/// audio frame. Calling `tick` after each data register access tells when a frame is complete, so
+/// the handler can do its heavy work only once per frame.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameCoalescer {
+    words_per_frame: u8,
+    count: u8,
+}
+
+impl FrameCoalescer {
+    /// Create a helper for frames made of `words_per_frame` half words.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `words_per_frame` is 0.
+    pub fn new(words_per_frame: u8) -> Self {
+        assert!(words_per_frame != 0, "words_per_frame must not be 0");
+        Self {
+            words_per_frame,
+            count: 0,
+        }
+    }
+
+    /// Record one data register access. Return `true` when this access completes a frame.
+    pub fn tick(&mut self) -> bool {
+        self.count += 1;
+        if self.count >= self.words_per_frame {
+            self.count = 0;
+            return true;
+        }
+        false
+    }
+
+    /// Restart from the beginning of a frame, for example after a resynchronisation.
+    pub fn reset(&mut self) {
+        self.count = 0;
+    }
 }
 
 /// Driver of a SPI peripheral in I2S mode.
@@ -744,6 +1102,15 @@ where
         self.i2s_peripheral
     }
 
+    /// Destroy the driver and release the owned i2s device without resetting it.
+    ///
+    /// Unlike `release`, generated clocks keep running, which allows to hand over a codec that
+    /// depends on a continuous master clock. The peripheral is left configured and enabled as
+    /// is, so it's up to the new owner to stop or reset it.
+    pub fn release_keep_clocks(self) -> I {
+        self.i2s_peripheral
+    }
+
     /// Consume the driver and create a new one with the given configuration.
     #[allow(non_camel_case_types)]
     pub fn reconfigure<NEW_MS, NEW_DIR, NEW_STD>(
@@ -753,6 +1120,73 @@ where
         let i2s_peripheral = self.i2s_peripheral;
         config.i2s_driver(i2s_peripheral)
     }
+
+    /// Apply the configuration at index `which` of `bank`, like `reconfigure` but in place.
+    ///
+    /// As when instantiating a driver, the peripheral is disabled and interrupts and DMA requests
+    /// are disabled.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `which` is not 0 or 1, or if an exact frequency is required by the
+    /// selected configuration and that frequency cannot be set.
+    pub fn apply_bank(&mut self, bank: &ConfigBank<MS, DIR, STD>, which: usize) {
+        bank.config(which)._write_registers(self);
+    }
+
+    /// Consume the driver and create a new one using another standard. The parameter is just a
+    /// marker implementing [`I2sStandard`](marker::I2sStandard).
+    ///
+    /// The peripheral is disabled and only the standard is changed, other settings are kept as
+    /// is. In master mode, the sampling rate is doubled when switching from a stereo standard to a
+    /// PCM one, and halved the other way.
+    #[allow(non_camel_case_types)]
+    pub fn change_standard<NEW_STD: marker::I2sStandard>(
+        mut self,
+        _standard: NEW_STD,
+    ) -> I2sDriver<I, MS, DIR, NEW_STD> {
+        self.disable();
+        self.registers().i2scfgr.modify(|_, w| {
+            _set_standard(w, NEW_STD::VALUE);
+            w
+        });
+        I2sDriver {
+            i2s_peripheral: self.i2s_peripheral,
+            _ms: PhantomData,
+            _tr: PhantomData,
+            _std: PhantomData,
+        }
+    }
+
+    /// Consume the driver and create a new one using another direction. The parameter is just a
+    /// marker implementing [`Direction`](marker::Direction).
+    ///
+    /// The peripheral is disabled and only the direction is changed, other settings are kept as
+    /// is. When leaving transmit mode, the caller should wait for the last data to be sent
+    /// before calling this.
+    #[allow(non_camel_case_types)]
+    pub fn change_direction<NEW_DIR: marker::Direction>(
+        mut self,
+        _direction: NEW_DIR,
+    ) -> I2sDriver<I, MS, NEW_DIR, STD> {
+        self.disable();
+        let master = self.is_master_configured();
+        self.registers().i2scfgr.modify(|_, w| {
+            match (master, NEW_DIR::VALUE) {
+                (false, TransmitOrReceive::Transmit) => w.i2scfg().slave_tx(),
+                (false, TransmitOrReceive::Receive) => w.i2scfg().slave_rx(),
+                (true, TransmitOrReceive::Transmit) => w.i2scfg().master_tx(),
+                (true, TransmitOrReceive::Receive) => w.i2scfg().master_rx(),
+            };
+            w
+        });
+        I2sDriver {
+            i2s_peripheral: self.i2s_peripheral,
+            _ms: PhantomData,
+            _tr: PhantomData,
+            _std: PhantomData,
+        }
+    }
 }
 
 /// Methods available in any mode
@@ -774,6 +1208,85 @@ where
         self.registers().i2scfgr.modify(|_, w| w.i2se().disabled());
     }
 
+    /// Return `true` if the I2S peripheral is enabled.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.registers().i2scfgr.read().i2se().bit()
+    }
+
+    /// Disable the I2S peripheral using the given mode.
+    ///
+    /// With `DisableMode::Immediate`, this is the same as `disable`. With `DisableMode::WhenIdle`,
+    /// this blocks until the BSY flag is cleared. Reading the status register while waiting may
+    /// clear some error flags, see [Status].
+    pub fn disable_with(&mut self, mode: DisableMode) {
+        if mode == DisableMode::WhenIdle {
+            while self.registers().sr.read().bsy().bit() {}
+        }
+        self.disable();
+    }
+
+    /// Get the clock polarity, read from the CKPOL bit.
+    pub fn clock_polarity(&self) -> ClockPolarity {
+        _clock_polarity(self.registers())
+    }
+
+    /// Get the PCM frame synchronisation, read from the PCMSYNC bit.
+    ///
+    /// This returns `None` if the I2SSTD field doesn't select a PCM standard.
+    pub fn pcm_sync(&self) -> Option<PcmSync> {
+        _pcm_sync(self.registers())
+    }
+
+    /// Return `true` if the peripheral is configured as master, according to the I2SCFG field.
+    ///
+    /// Unlike the `MS` typestate, this reflects the actual content of the register.
+    pub fn is_master_configured(&self) -> bool {
+        let i2scfg = self.registers().i2scfgr.read().i2scfg();
+        i2scfg.is_master_tx() || i2scfg.is_master_rx()
+    }
+
+    /// Change the data format without reconfiguring the whole peripheral.
+    ///
+    /// The configuration can't be changed while the peripheral is enabled, so the peripheral is
+    /// disabled first. The prescaler is kept as is, so the sampling rate changes when the channel
+    /// length changes while master clock is disabled.
+    pub fn set_data_format(&mut self, format: DataFormat) {
+        self.disable();
+        self.registers().i2scfgr.modify(|_, w| {
+            _set_data_format(w, format);
+            w
+        });
+    }
+
+    /// Create a [`FrameCoalescer`] matching the current configuration.
+    ///
+    /// A frame is made of one half word per channel with 16 bit data, and two otherwise. There is
+    /// one channel with PCM standards and two with others.
+    pub fn frame_coalescer(&self) -> FrameCoalescer {
+        let i2scfgr = self.registers().i2scfgr.read();
+        let nb_chan = if i2scfgr.i2sstd().is_pcm() { 1 } else { 2 };
+        let words_per_chan = if i2scfgr.datlen().is_sixteen_bit() {
+            1
+        } else {
+            2
+        };
+        FrameCoalescer::new(nb_chan * words_per_chan)
+    }
+
+    /// Get the number of significant bits of a sample, that is 16, 24 or 32.
+    ///
+    /// This is read from the DATLEN field and can be smaller than the channel length.
+    pub fn data_bits(&self) -> u8 {
+        let datlen = self.registers().i2scfgr.read().datlen();
+        if datlen.is_sixteen_bit() {
+            16
+        } else if datlen.is_twenty_four_bit() {
+            24
+        } else {
+            32
+        }
+    }
+
     /// Return `true` if the level on the WS line is high.
     #[deprecated(
         since = "0.4.0",
@@ -818,6 +1331,91 @@ where
             _std: PhantomData,
         }
     }
+
+    /// Get the content of the status register and the level of the WS line, `true` meaning high.
+    ///
+    /// This is meant for custom synchronisation loops, where `status` and `ws_pin` can't be
+    /// borrowed together. The status register is read first. Like `status`, this may change the
+    /// register value.
+    pub fn status_and_ws(&mut self) -> (Status<MS, DIR, STD>, bool) {
+        let status = self.status();
+        (status, self.i2s_peripheral.ws_pin().is_high())
+    }
+
+    /// Fill `out` with consecutive raw reads of the status register, as fast as possible.
+    ///
+    /// This is a diagnostic tool to analyse offline how flags like TXE, RXNE or BSY change over
+    /// time. Like `status`, reading the status register may clear error flags, so an error flag
+    /// may only appear in a single sample, and may not be seen by the following `status` calls.
+    pub fn sample_status(&mut self, out: &mut [u16]) {
+        let registers = self.registers();
+        for sample in out {
+            *sample = registers.sr.read().bits() as u16;
+        }
+    }
+
+    /// Read all registers except the data register in one shot.
+    ///
+    /// Like `status`, reading the status register may clear some error flags, see [Status]. Use
+    /// [`register_dump_without_sr`](Self::register_dump_without_sr) to avoid that.
+    pub fn register_dump(&mut self) -> RegisterDump {
+        let mut dump = self.register_dump_without_sr();
+        dump.sr = Some(self.registers().sr.read().bits() as u16);
+        dump
+    }
+
+    /// Read all registers except the data and the status registers, so reading has no side
+    /// effect. The `sr` field of the result is `None`.
+    pub fn register_dump_without_sr(&self) -> RegisterDump {
+        let registers = self.registers();
+        RegisterDump {
+            cr1: registers.cr1.read().bits() as u16,
+            cr2: registers.cr2.read().bits() as u16,
+            sr: None,
+            crcpr: registers.crcpr.read().bits() as u16,
+            rxcrcr: registers.rxcrcr.read().bits() as u16,
+            txcrcr: registers.txcrcr.read().bits() as u16,
+            i2scfgr: registers.i2scfgr.read().bits() as u16,
+            i2spr: registers.i2spr.read().bits() as u16,
+        }
+    }
+}
+
+/// CRC methods, experimental
+///
+/// CRC calculation is a SPI feature that is not part of any I2S standard. The reference manuals
+/// don't describe its behavior in I2S mode, so these methods are advanced and experimental. Both
+/// sides of a link are assumed to compute the CRC in the same way over a same sequence of data.
+///
+/// CRC calculation is enabled by setting the CRCEN bit of CR1 while the peripheral is disabled.
+/// Contrary to SPI mode, no CRC is sent or checked by hardware at the end of a transfer, so the
+/// CRCERR flag is meaningless.
+impl<I, MS, DIR, STD> I2sDriver<I, MS, DIR, STD>
+where
+    I: I2sPeripheral,
+{
+    /// Get the content of the RXCRCR register.
+    ///
+    /// This is the CRC computed over the received data since CRC registers were cleared.
+    pub fn rx_crc(&self) -> u16 {
+        self.registers().rxcrcr.read().rx_crc().bits()
+    }
+
+    /// Get the content of the TXCRCR register.
+    ///
+    /// This is the CRC computed over the transmitted data since CRC registers were cleared.
+    pub fn tx_crc(&self) -> u16 {
+        self.registers().txcrcr.read().tx_crc().bits()
+    }
+
+    /// Clear both CRC registers by toggling the CRCEN bit.
+    ///
+    /// CRC calculation is left enabled. The peripheral should be disabled when calling this.
+    pub fn reset_crc(&mut self) {
+        let registers = self.registers();
+        registers.cr1.modify(|_, w| w.crcen().disabled());
+        registers.cr1.modify(|_, w| w.crcen().enabled());
+    }
 }
 
 /// Master-only methods
@@ -841,12 +1439,58 @@ where
         registers.i2scfgr.write(|w| unsafe { w.bits(i2scfgr) });
     }
 
+    /// Reset clocks if the BSY flag stays set during `max_polls` reads of the status register.
+    ///
+    /// This is a workaround for chips whose errata report a BSY flag that can get stuck, usually
+    /// along with corrupted data. Check the errata of your chip to know whether this is needed.
+    /// Return `true` if the recovery was triggered. Reading the status register may clear some
+    /// error flags, see [Status].
+    pub fn recover_if_stuck(&mut self, max_polls: u32) -> bool {
+        for _ in 0..max_polls {
+            if !self.registers().sr.read().bsy().bit() {
+                return false;
+            }
+        }
+        self.reset_clocks();
+        true
+    }
+
     /// Get the actual sample rate imposed by the driver.
     ///
     /// This allows client code to check deviation from the requested frequency.
     pub fn sample_rate(&self) -> u32 {
         _sample_rate(self.registers(), self.i2s_peripheral.i2s_freq())
     }
+
+    /// Get the current prescaler setting as an `(odd, div)` pair.
+    ///
+    /// When the configuration used `request_frequency`, passing this to
+    /// [`I2sDriverConfig::prescaler`] in a new configuration keeps exactly the current sampling
+    /// rate, as long as master clock, standard and data format are unchanged.
+    pub fn prescaler(&self) -> (bool, u8) {
+        let i2spr = self.registers().i2spr.read();
+        (i2spr.odd().bit(), i2spr.i2sdiv().bits())
+    }
+
+    /// Get the ratio between master clock frequency and sample rate.
+    ///
+    /// This is 256 with Philips, Msb and Lsb standards and 128 with PCM standards. When master
+    /// clock is enabled, its frequency is `master_clock_ratio() * sample_rate()`.
+    pub fn master_clock_ratio(&self) -> u32 {
+        _master_clock_ratio(self.registers())
+    }
+
+    /// Get the number of master clock cycles per sample, or `None` if master clock is disabled.
+    ///
+    /// This is [`master_clock_ratio`](Self::master_clock_ratio), named after codec datasheets
+    /// giving master clock frequency as `N × fs`.
+    pub fn mclk_cycles_per_sample(&self) -> Option<u32> {
+        if self.registers().i2spr.read().mckoe().bit() {
+            Some(self.master_clock_ratio())
+        } else {
+            None
+        }
+    }
 }
 
 /// Transmit-only methods
@@ -861,6 +1505,15 @@ where
         self.registers().dr.write(|w| w.dr().bits(value));
     }
 
+    /// Get the number of half words that can be written to the data register without waiting.
+    ///
+    /// SPI version 1.2 has no FIFO, only a single Tx buffer, so this returns 1 when the TXE flag
+    /// is set and 0 otherwise. Like `status`, this reads the status register and may clear some
+    /// error flags.
+    pub fn writable_words(&mut self) -> usize {
+        self.status().txe() as usize
+    }
+
     /// When set to `true`, an interrupt is generated each time the Tx buffer is empty.
     pub fn set_tx_interrupt(&mut self, enabled: bool) {
         self.registers().cr2.modify(|_, w| w.txeie().bit(enabled))
@@ -882,6 +1535,47 @@ where
         self.registers().dr.read().dr().bits()
     }
 
+    /// Read a raw value from the Rx buffer as two bytes and delete the RXNE flag in status
+    /// register.
+    ///
+    /// Bytes are in wire order: since data are transferred MSB first, the first byte is the one
+    /// received first. This is meant for nonstandard framing, like 8 bit samples packed in a half
+    /// word.
+    pub fn read_data_register_bytes(&mut self) -> [u8; 2] {
+        self.read_data_register().to_be_bytes()
+    }
+
+    /// Read a 32 bit sample as two consecutive half words, checking for overrun.
+    ///
+    /// This returns `WouldBlock` if the Rx buffer is empty. Once the most significant half is
+    /// read, this waits for the least significant one, which comes within 16 bit clock cycles.
+    /// The caller is responsible for calling this at a sample boundary. On overrun, a sample has
+    /// been lost and this alignment can't be trusted anymore.
+    pub fn read_frame32_checked(&mut self) -> nb::Result<i32, I2sTransferError> {
+        let status = self.status();
+        if status.ovr() {
+            return Err(nb::Error::Other(I2sTransferError::Overrun));
+        }
+        if !status.rxne() {
+            return Err(nb::Error::WouldBlock);
+        }
+        let msb = self.read_data_register();
+        loop {
+            let status = self.status();
+            if status.ovr() {
+                return Err(nb::Error::Other(I2sTransferError::Overrun));
+            }
+            if status.rxne() {
+                break;
+            }
+        }
+        let lsb = self.read_data_register();
+        if self.status().ovr() {
+            return Err(nb::Error::Other(I2sTransferError::Overrun));
+        }
+        Ok(((msb as u32) << 16 | lsb as u32) as i32)
+    }
+
     /// When set to `true`, an interrupt is generated each time the Rx buffer contains a new data.
     pub fn set_rx_interrupt(&mut self, enabled: bool) {
         self.registers().cr2.modify(|_, w| w.rxneie().bit(enabled))
@@ -919,7 +1613,7 @@ where
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// Dual I2S driver configuration. This can be used as a dual I2S driver builder.
 ///
 ///  - `MS`: `Master` or `Slave`. It applies to the "main" part only since the extension is always
@@ -940,6 +1634,7 @@ pub struct DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD> {
     data_format: DataFormat,
     master_clock: bool,
     frequency: Frequency,
+    assumed_clock: Option<u32>,
 
     _ms: PhantomData<MS>,
     _main_dir: PhantomData<MAIN_DIR>,
@@ -959,6 +1654,7 @@ impl DualI2sDriverConfig<Slave, Transmit, Transmit, Philips> {
             data_format: Default::default(),
             master_clock: false,
             frequency: Frequency::Prescaler(false, 0b10),
+            assumed_clock: None,
             _ms: PhantomData,
             _main_dir: PhantomData,
             _ext_dir: PhantomData,
@@ -979,6 +1675,36 @@ impl DualI2sDriverConfig<Master, Transmit, Transmit, Philips> {
             data_format: Default::default(),
             master_clock: false,
             frequency: Frequency::Prescaler(false, 0b10),
+            assumed_clock: None,
+            _ms: PhantomData,
+            _main_dir: PhantomData,
+            _ext_dir: PhantomData,
+            _std: PhantomData,
+        }
+    }
+}
+
+#[allow(non_camel_case_types)]
+impl<MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD>
+where
+    EXT_DIR: Direction,
+{
+    /// Create a dual configuration from a single one, used for the main part, and the direction
+    /// of the extension part (`Transmit` or `Receive`).
+    ///
+    /// Standard, clock polarity, data format, master clock and frequency settings are copied from
+    /// `config`, so the same settings don't need to be repeated for both kinds of drivers.
+    pub fn from_single(config: I2sDriverConfig<MS, MAIN_DIR, STD>, _ext_dir: EXT_DIR) -> Self {
+        Self {
+            slave_or_master: config.slave_or_master,
+            main_dir: config.transmit_or_receive,
+            ext_dir: EXT_DIR::VALUE,
+            standard: config.standard,
+            clock_polarity: config.clock_polarity,
+            data_format: config.data_format,
+            master_clock: config.master_clock,
+            frequency: config.frequency,
+            assumed_clock: config.assumed_clock,
             _ms: PhantomData,
             _main_dir: PhantomData,
             _ext_dir: PhantomData,
@@ -1004,6 +1730,10 @@ impl<MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD>
             main: I2sCore::new(),
             ext: I2sCore::new(),
         };
+        let i2s_freq = || {
+            self.assumed_clock
+                .unwrap_or_else(|| driver.dual_i2s_peripheral.i2s_freq())
+        };
         // main peripheral setup
         driver.main.registers().cr1.reset(); // ensure SPI is disabled
         driver.main.registers().cr2.reset(); // disable interrupt and DMA request
@@ -1015,21 +1745,9 @@ impl<MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD>
                 (SlaveOrMaster::Master, TransmitOrReceive::Transmit) => w.i2scfg().master_tx(),
                 (SlaveOrMaster::Master, TransmitOrReceive::Receive) => w.i2scfg().master_rx(),
             };
-            match self.standard {
-                I2sStandard::Philips => w.i2sstd().philips(),
-                I2sStandard::Msb => w.i2sstd().msb(),
-                I2sStandard::Lsb => w.i2sstd().lsb(),
-                I2sStandard::PcmShortSync => w.i2sstd().pcm().pcmsync().short(),
-                I2sStandard::PcmLongSync => w.i2sstd().pcm().pcmsync().long(),
-            };
-            match self.data_format {
-                DataFormat::Data16Channel16 => w.datlen().sixteen_bit().chlen().sixteen_bit(),
-                DataFormat::Data16Channel32 => w.datlen().sixteen_bit().chlen().thirty_two_bit(),
-                DataFormat::Data24Channel32 => {
-                    w.datlen().twenty_four_bit().chlen().thirty_two_bit()
-                }
-                DataFormat::Data32Channel32 => w.datlen().thirty_two_bit().chlen().thirty_two_bit(),
-            };
+            _set_standard(w, self.standard);
+            _set_clock_polarity(w, self.clock_polarity);
+            _set_data_format(w, self.data_format);
             w
         });
         driver.main.registers().i2spr.write(|w| {
@@ -1038,7 +1756,7 @@ impl<MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD>
                 Frequency::Prescaler(odd, div) => _set_prescaler(w, odd, div),
                 Frequency::Request(freq) => _set_request_frequency(
                     w,
-                    driver.dual_i2s_peripheral.i2s_freq(),
+                    i2s_freq(),
                     freq,
                     self.master_clock,
                     self.standard,
@@ -1046,7 +1764,7 @@ impl<MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD>
                 ),
                 Frequency::Require(freq) => _set_require_frequency(
                     w,
-                    driver.dual_i2s_peripheral.i2s_freq(),
+                    i2s_freq(),
                     freq,
                     self.master_clock,
                     self.standard,
@@ -1064,21 +1782,9 @@ impl<MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD>
                 (_, TransmitOrReceive::Transmit) => w.i2scfg().slave_tx(),
                 (_, TransmitOrReceive::Receive) => w.i2scfg().slave_rx(),
             };
-            match self.standard {
-                I2sStandard::Philips => w.i2sstd().philips(),
-                I2sStandard::Msb => w.i2sstd().msb(),
-                I2sStandard::Lsb => w.i2sstd().lsb(),
-                I2sStandard::PcmShortSync => w.i2sstd().pcm().pcmsync().short(),
-                I2sStandard::PcmLongSync => w.i2sstd().pcm().pcmsync().long(),
-            };
-            match self.data_format {
-                DataFormat::Data16Channel16 => w.datlen().sixteen_bit().chlen().sixteen_bit(),
-                DataFormat::Data16Channel32 => w.datlen().sixteen_bit().chlen().thirty_two_bit(),
-                DataFormat::Data24Channel32 => {
-                    w.datlen().twenty_four_bit().chlen().thirty_two_bit()
-                }
-                DataFormat::Data32Channel32 => w.datlen().thirty_two_bit().chlen().thirty_two_bit(),
-            };
+            _set_standard(w, self.standard);
+            _set_clock_polarity(w, self.clock_polarity);
+            _set_data_format(w, self.data_format);
             w
         });
         driver.ext.registers().i2spr.write(|w| {
@@ -1087,7 +1793,7 @@ impl<MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD>
                 Frequency::Prescaler(odd, div) => _set_prescaler(w, odd, div),
                 Frequency::Request(freq) => _set_request_frequency(
                     w,
-                    driver.dual_i2s_peripheral.i2s_freq(),
+                    i2s_freq(),
                     freq,
                     self.master_clock,
                     self.standard,
@@ -1095,7 +1801,7 @@ impl<MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD>
                 ),
                 Frequency::Require(freq) => _set_require_frequency(
                     w,
-                    driver.dual_i2s_peripheral.i2s_freq(),
+                    i2s_freq(),
                     freq,
                     self.master_clock,
                     self.standard,
@@ -1138,6 +1844,7 @@ impl<MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD>
             data_format: self.data_format,
             master_clock: self.master_clock,
             frequency: self.frequency,
+            assumed_clock: self.assumed_clock,
             _ms: PhantomData,
             _main_dir: PhantomData,
             _ext_dir: PhantomData,
@@ -1162,6 +1869,7 @@ impl<MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD>
             data_format: self.data_format,
             master_clock: self.master_clock,
             frequency: self.frequency,
+            assumed_clock: self.assumed_clock,
             _ms: PhantomData,
             _main_dir: PhantomData,
             _ext_dir: PhantomData,
@@ -1203,6 +1911,7 @@ impl<MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD>
             data_format,
             master_clock: false,
             frequency: Frequency::Prescaler(false, 0b10),
+            assumed_clock: None,
             _ms: PhantomData,
             _main_dir: PhantomData,
             _ext_dir: PhantomData,
@@ -1220,6 +1929,7 @@ impl<MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD>
             data_format,
             master_clock,
             frequency,
+            assumed_clock,
             ..
         } = self;
         DualI2sDriverConfig::<Master, MAIN_DIR, EXT_DIR, STD> {
@@ -1231,6 +1941,7 @@ impl<MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<MS, MAIN_DIR, EXT_DIR, STD>
             data_format,
             master_clock,
             frequency,
+            assumed_clock,
             _ms: PhantomData,
             _main_dir: PhantomData,
             _ext_dir: PhantomData,
@@ -1289,6 +2000,15 @@ impl<MAIN_DIR, EXT_DIR, STD> DualI2sDriverConfig<Master, MAIN_DIR, EXT_DIR, STD>
         self.frequency = Frequency::Require(freq);
         self
     }
+
+    /// Use `freq` as I2S clock source frequency when computing the prescaler of a requested or
+    /// required frequency, instead of asking the peripheral.
+    ///
+    /// See [`I2sDriverConfig::with_assumed_clock`].
+    pub fn with_assumed_clock(mut self, freq: u32) -> Self {
+        self.assumed_clock = Some(freq);
+        self
+    }
 }
 
 /// Main or extension part of a `DualI2sDriver`.
@@ -1391,6 +2111,23 @@ where
     pub fn set_tx_dma(&mut self, enabled: bool) {
         self.registers().cr2.modify(|_, w| w.txdmaen().bit(enabled))
     }
+
+    /// Write (blocking) a whole audio frame, using the same frame types as
+    /// [`I2sTransfer`](crate::transfer::I2sTransfer). The data format is given by a marker.
+    ///
+    /// Each half word of the frame is written as soon as the Tx buffer is empty. This doesn't
+    /// handle synchronisation: it's up to the caller to call this at a frame boundary. Since this
+    /// blocks during about one frame, the other part should be handled by DMA or interrupts.
+    pub fn write_frame<FMT, T>(&mut self, _format: FMT, frame: T)
+    where
+        (STD, FMT): FrameFormat,
+        T: ToRawFrame<STD, FMT>,
+    {
+        for &word in frame.to_raw().as_ref() {
+            while !self.status().txe() {}
+            self.write_data_register(word);
+        }
+    }
 }
 
 /// Receive-only methods
@@ -1404,6 +2141,26 @@ where
         self.registers().dr.read().dr().bits()
     }
 
+    /// Read (blocking) a whole audio frame, using the same frame types as
+    /// [`I2sTransfer`](crate::transfer::I2sTransfer). The data format is given by a marker.
+    ///
+    /// Each half word of the frame is read as soon as the Rx buffer contains data. This doesn't
+    /// handle synchronisation nor errors: it's up to the caller to call this at a frame boundary.
+    /// Since this blocks during about one frame, the other part should be handled by DMA or
+    /// interrupts.
+    pub fn read_frame<FMT, T>(&mut self, _format: FMT) -> T
+    where
+        (STD, FMT): FrameFormat,
+        T: FromRawFrame<STD, FMT>,
+    {
+        let mut raw = <(STD, FMT) as FrameFormat>::RawFrame::default();
+        for word in raw.as_mut() {
+            while !self.status().rxne() {}
+            *word = self.read_data_register();
+        }
+        T::from_raw(raw)
+    }
+
     /// When set to `true`, an interrupt is generated each time the Rx buffer contains a new data.
     pub fn set_rx_interrupt(&mut self, enabled: bool) {
         self.registers().cr2.modify(|_, w| w.rxneie().bit(enabled))
@@ -1515,6 +2272,102 @@ where
     pub fn ws_pin_mut(&mut self) -> &mut I::WsPin {
         self.dual_i2s_peripheral.ws_pin_mut()
     }
+
+    /// Return `true` if the parts are configured in a way allowing a loopback test, that is main
+    /// output wired to ext input or the reverse.
+    ///
+    /// This checks from registers that both parts are in I2S mode with opposite directions, the
+    /// extension being slave, and that they use the same standard, clock polarity and data
+    /// format.
+    pub fn can_loopback(&self) -> bool {
+        let main = self.main.registers().i2scfgr.read();
+        let ext = self.ext.registers().i2scfgr.read();
+        let main_receive = main.i2scfg().is_slave_rx() || main.i2scfg().is_master_rx();
+        let ext_receive = ext.i2scfg().is_slave_rx();
+        let ext_slave = ext.i2scfg().is_slave_tx() || ext.i2scfg().is_slave_rx();
+        main.i2smod().is_i2smode()
+            && ext.i2smod().is_i2smode()
+            && ext_slave
+            && main_receive != ext_receive
+            && main.i2sstd().bits() == ext.i2sstd().bits()
+            && main.pcmsync().bit() == ext.pcmsync().bit()
+            && main.ckpol().bit() == ext.ckpol().bit()
+            && main.datlen().bits() == ext.datlen().bits()
+            && main.chlen().bit() == ext.chlen().bit()
+    }
+
+    /// Return `true` if both parts are processing the same channel, according to the CHSIDE flag
+    /// of their status registers.
+    ///
+    /// A mismatch means the full duplex link has slipped by a channel. With PCM standards, the
+    /// channel flag is meaningless, so this always returns `true`. Note the CHSIDE flag may be
+    /// sporadically wrong in master transmit mode. Reading status registers may clear some error
+    /// flags, see [Status].
+    pub fn frames_aligned(&mut self) -> bool {
+        if self.main.registers().i2scfgr.read().i2sstd().is_pcm() {
+            return true;
+        }
+        let main = self.main.registers().sr.read().chside().bit();
+        let ext = self.ext.registers().sr.read().chside().bit();
+        main == ext
+    }
+
+    /// Get the clock polarity of the main part, read from its CKPOL bit. The extension part is
+    /// configured with the same polarity.
+    pub fn clock_polarity(&self) -> ClockPolarity {
+        _clock_polarity(self.main.registers())
+    }
+
+    /// Get the raw content of the I2SCFGR and I2SPR registers of the main part, in this order.
+    ///
+    /// This is intended for diagnostics.
+    pub fn main_config_bits(&self) -> (u16, u16) {
+        let registers = self.main.registers();
+        (
+            registers.i2scfgr.read().bits() as u16,
+            registers.i2spr.read().bits() as u16,
+        )
+    }
+
+    /// Get the raw content of the I2SCFGR and I2SPR registers of the extension part, in this
+    /// order.
+    ///
+    /// This is intended for diagnostics, e.g. to check that the extension configuration matches
+    /// the main one.
+    pub fn ext_config_bits(&self) -> (u16, u16) {
+        let registers = self.ext.registers();
+        (
+            registers.i2scfgr.read().bits() as u16,
+            registers.i2spr.read().bits() as u16,
+        )
+    }
+}
+
+/// DMA setup
+#[allow(non_camel_case_types)]
+impl<I, MS, MAIN_DIR, EXT_DIR, STD> DualI2sDriver<I, MS, MAIN_DIR, EXT_DIR, STD>
+where
+    I: DualI2sPeripheral,
+    MAIN_DIR: Direction,
+    EXT_DIR: Direction,
+{
+    /// Enable or disable DMA requests of both parts in one call.
+    ///
+    /// `tx` applies to the part(s) configured for transmission, and `rx` to the part(s)
+    /// configured for reception. This is equivalent to call `set_tx_dma` or `set_rx_dma` on each
+    /// part.
+    pub fn enable_dma(&mut self, tx: bool, rx: bool) {
+        let parts = [
+            (MAIN_DIR::VALUE, self.main.registers()),
+            (EXT_DIR::VALUE, self.ext.registers()),
+        ];
+        for (dir, registers) in parts {
+            match dir {
+                TransmitOrReceive::Transmit => registers.cr2.modify(|_, w| w.txdmaen().bit(tx)),
+                TransmitOrReceive::Receive => registers.cr2.modify(|_, w| w.rxdmaen().bit(rx)),
+            }
+        }
+    }
 }
 
 /// Master-only methods
@@ -1563,6 +2416,52 @@ where
     }
 }
 
+/// Error interrupts, Slave Mode.
+#[allow(non_camel_case_types)]
+impl<I, MAIN_DIR, EXT_DIR, STD> DualI2sDriver<I, Slave, MAIN_DIR, EXT_DIR, STD>
+where
+    I: DualI2sPeripheral,
+{
+    /// Enable or disable error interrupts of both parts in one call.
+    ///
+    /// This is equivalent to call `set_error_interrupt` on the main and the extension parts.
+    pub fn set_error_interrupts(&mut self, main: bool, ext: bool) {
+        self.main.set_error_interrupt(main);
+        self.ext.set_error_interrupt(ext);
+    }
+}
+
+/// Error interrupts, Master Receive Mode.
+#[allow(non_camel_case_types)]
+impl<I, EXT_DIR, STD> DualI2sDriver<I, Master, Receive, EXT_DIR, STD>
+where
+    I: DualI2sPeripheral,
+{
+    /// Enable or disable error interrupts of both parts in one call.
+    ///
+    /// This is equivalent to call `set_error_interrupt` on the main and the extension parts.
+    pub fn set_error_interrupts(&mut self, main: bool, ext: bool) {
+        self.main.set_error_interrupt(main);
+        self.ext.set_error_interrupt(ext);
+    }
+}
+
+/// Error interrupts, Master Transmit Mode.
+#[allow(non_camel_case_types)]
+impl<I, EXT_DIR, STD> DualI2sDriver<I, Master, Transmit, EXT_DIR, STD>
+where
+    I: DualI2sPeripheral,
+{
+    /// Enable or disable error interrupts of the dual driver in one call.
+    ///
+    /// No error can occur on a Master Transmit main part, so only the extension part, which is a
+    /// slave and can overrun or underrun, has an error interrupt. This is equivalent to call
+    /// `set_error_interrupt` on the extension part, typically a receiver in full duplex setups.
+    pub fn set_error_interrupts(&mut self, ext: bool) {
+        self.ext.set_error_interrupt(ext);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1575,4 +2474,69 @@ mod tests {
             assert_eq!(res, check);
         }
     }
+
+    #[test]
+    fn test_diagnose() {
+        let config = I2sDriverConfig::new_master().require_frequency(48_000);
+        let diagnostics = config.diagnose(12_288_000);
+        assert!(diagnostics.is_ok());
+        assert_eq!(diagnostics.effective_frequency, 48_000);
+        let diagnostics = config.diagnose(12_000_000);
+        assert!(diagnostics.required_frequency_unreachable);
+        assert!(!diagnostics.division_out_of_range);
+        let diagnostics = config.request_frequency(1_000_000).diagnose(12_288_000);
+        assert!(diagnostics.division_out_of_range);
+        assert!(!diagnostics.is_ok());
+        assert!(config.is_rate_exact(12_288_000, 48_000));
+        assert!(!config.is_rate_exact(12_000_000, 48_000));
+        assert!(!config.is_rate_exact(12_288_000, 0));
+        let diagnostics = config.request_frequency(0).diagnose(12_288_000);
+        assert!(diagnostics.division_out_of_range);
+        let diagnostics = config.request_frequency(u32::MAX).diagnose(12_288_000);
+        assert!(diagnostics.division_out_of_range);
+        let diagnostics = config.require_frequency(u32::MAX).diagnose(12_288_000);
+        assert!(diagnostics.required_frequency_unreachable);
+        let mut table = [(1, 1); 2];
+        config.rate_table(12_288_000, &[48_000, u32::MAX], &mut table);
+        assert_eq!(table, [(48_000, 0), (0, -1_000_000)]);
+    }
+
+    #[test]
+    fn test_from_single_assumed_clock() {
+        let single = I2sDriverConfig::new_master()
+            .request_frequency(48_000)
+            .with_assumed_clock(12_288_000);
+        let dual = DualI2sDriverConfig::from_single(single, Receive);
+        let expected = DualI2sDriverConfig::new_master()
+            .direction(Transmit, Receive)
+            .request_frequency(48_000);
+        assert_ne!(dual, expected);
+        assert_eq!(dual, expected.with_assumed_clock(12_288_000));
+    }
+
+    // each test using a mock peripheral must use its own slot
+    #[test]
+    fn test_driver_config_registers() {
+        use crate::mock::{MockI2sPeripheral, Register};
+        let driver = I2sDriverConfig::new_master()
+            .receive()
+            .standard(PcmLongSync)
+            .data_format(DataFormat::Data24Channel32)
+            .clock_polarity(ClockPolarity::IdleHigh)
+            .master_clock(true)
+            .prescaler(true, 3)
+            .i2s_driver(MockI2sPeripheral::<0>::new(12_288_000));
+        let i2scfgr = crate::mock::register_block(0).read(Register::I2scfgr);
+        // I2SMOD, I2SCFG = master rx, PCMSYNC, I2SSTD = PCM, CKPOL, DATLEN = 24 bits, CHLEN
+        assert_eq!(
+            i2scfgr,
+            1 << 11 | 0b11 << 8 | 1 << 7 | 0b11 << 4 | 1 << 3 | 0b01 << 1 | 1
+        );
+        assert_eq!(driver.clock_polarity(), ClockPolarity::IdleHigh);
+        let i2spr = crate::mock::register_block(0).read(Register::I2spr);
+        assert_eq!(i2spr, 1 << 9 | 1 << 8 | 3);
+        assert_eq!(driver.sample_rate(), 12_288_000 / (128 * 7));
+        let peripheral = driver.release();
+        assert_eq!(peripheral.rcc_reset_count(), 1);
+    }
 }
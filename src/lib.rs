@@ -16,6 +16,9 @@
 //! to create some examples. For reference, implementation and examples are (or will be soon)
 //! available in [stm32f4xx-hal](https://github.com/stm32-rs/stm32f4xx-hal/).
 //!
+//! The `test-util` feature enables the [`mock`] module, providing mock peripherals backed by
+//! in-memory register blocks to test code without hardware.
+//!
 //! # For I2S users
 //!
 //! You should use use this library through a MCU HAL. For fine control and advanced usage,
@@ -30,6 +33,17 @@
 //!  the bit rate is the same with half the samples.
 //!  - When master clock is enabled, its frequency is 128 * sampling_frequency, instead of 256 *
 //!  sampling_frequency.
+//!  - The polarity of the frame synchronisation is fixed: WS is high during the synchronisation
+//!    pulse, with either short or long synchronisation. SPI version 1.2 has no setting to invert
+//!    it, so a codec expecting an active low synchronisation needs an external inverter.
+//!
+//! # About TI frame format
+//!
+//! SPI peripherals have a FRF bit to select the TI frame format, but this bit is not used in I2S
+//! mode, so TI mode can't be reached with this library. For codecs expecting a TI (or "DSP")
+//! style framing, `PcmShortSync` is the closest standard: the frame synchronisation is a pulse one
+//! bit clock long, issued just before the first bit of data. Check the timing diagrams of both
+//! datasheets before relying on it.
 //!
 //! # Issues and limitations
 //! - In master transmit mode, the CHSIDE flag appears to be sporadically wrong, so don't use it.
@@ -41,8 +55,15 @@
 
 mod pac;
 
+pub mod clock;
+pub mod codecs;
+pub mod convert;
 pub mod driver;
 pub mod marker;
+pub mod mix;
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock;
+pub mod sine;
 pub mod transfer;
 
 mod sealed {
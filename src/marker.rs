@@ -3,63 +3,63 @@
 use crate::sealed::Sealed;
 
 /// Marker, indicated master mode.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Master;
 
 /// Marker, indicate slave mode.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Slave;
 
 /// Marker, indicated transmit mode.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Transmit;
 
 /// Marker, indicate receive mode.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Receive;
 
 /// Marker, indicate 16 bits data length on 16 bits wide channel.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Data16Channel16;
 
 /// Marker, indicate 16 bits data length on 32 bits wide channel.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Data16Channel32;
 
 /// Marker, indicate 24 bits data length on 32 bits wide channel.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Data24Channel32;
 
 /// Marker, indicate 32 bits data length on 32 bits wide channel.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Data32Channel32;
 
 /// Marker, indicate Philips I2S standard.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Philips;
 
 /// Marker, indicate MSB Justified standard.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Msb;
 
 /// Marker, indicate LSB Justified standard.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Lsb;
 
 /// Marker, indicate PCM standard with short frame synchronisation.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct PcmShortSync;
 
 /// Marker, indicate PCM standard with long frame synchronisation.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct PcmLongSync;
 
 /// Marker, indicate main part of a dual i2s device.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Main;
 
 /// Marker, indicate extension part of a dual i2s device.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Ext;
 
 impl Sealed for Master {}
@@ -78,6 +78,18 @@ impl Sealed for PcmLongSync {}
 impl Sealed for Main {}
 impl Sealed for Ext {}
 
+/// Trait for `Master` and `Slave` markers.
+pub trait Role: Sealed {
+    /// `true` for `Master`, `false` for `Slave`.
+    const IS_MASTER: bool;
+}
+impl Role for Master {
+    const IS_MASTER: bool = true;
+}
+impl Role for Slave {
+    const IS_MASTER: bool = false;
+}
+
 /// Trait for `Transmit` and `Receive` markers.
 pub trait Direction: Sealed {
     /// Internal use only (used by configuration types).
@@ -0,0 +1,47 @@
+//! Helpers to mix audio frames.
+//!
+//! This is typically used to combine frames received from two sources before writing them to a
+//! transmitting transfer. Additions saturate, so a loud input is clipped instead of wrapping
+//! around to the opposite sign.
+
+/// Audio frames that can be mixed with [`saturating_mix`].
+///
+/// This is implemented for `i16` and `i32` samples, and for `(i16, i16)` and `(i32, i32)` stereo
+/// frames.
+pub trait Mix: Copy {
+    /// Add two frames channel-wise, with saturation.
+    fn saturating_mix(self, other: Self) -> Self;
+}
+
+impl Mix for i16 {
+    fn saturating_mix(self, other: Self) -> Self {
+        self.saturating_add(other)
+    }
+}
+
+impl Mix for i32 {
+    fn saturating_mix(self, other: Self) -> Self {
+        self.saturating_add(other)
+    }
+}
+
+impl<T: Mix> Mix for (T, T) {
+    fn saturating_mix(self, other: Self) -> Self {
+        (
+            self.0.saturating_mix(other.0),
+            self.1.saturating_mix(other.1),
+        )
+    }
+}
+
+/// Add two audio frames channel-wise, with saturation.
+///
+/// ```
+/// # use stm32_i2s_v12x::mix::saturating_mix;
+/// assert_eq!(saturating_mix((1000i16, -1000), (2000, -2000)), (3000, -3000));
+/// assert_eq!(saturating_mix((i16::MAX, i16::MIN), (1, -1)), (i16::MAX, i16::MIN));
+/// assert_eq!(saturating_mix(i32::MAX - 1, 2), i32::MAX);
+/// ```
+pub fn saturating_mix<T: Mix>(a: T, b: T) -> T {
+    a.saturating_mix(b)
+}
@@ -0,0 +1,310 @@
+//! Mock peripherals to exercise [`I2sDriver`](crate::driver::I2sDriver),
+//! [`DualI2sDriver`](crate::driver::DualI2sDriver) and
+//! [`I2sTransfer`](crate::transfer::I2sTransfer) without hardware.
+//!
+//! This module is only available with the `test-util` feature.
+//!
+//! Register blocks of mock peripherals are plain memory taken from a static pool of [`SLOTS`]
+//! blocks. The slot used by a mock peripheral is selected with a const generic parameter, and two
+//! peripherals used at the same time must not share a slot. Since tests run in parallel, each test
+//! should use its own slot.
+//!
+//! Since register blocks are plain memory, there is no hardware behavior: status flags only
+//! change when they are written by the test, reading the data register doesn't clear RXNE,
+//! writing it doesn't clear TXE, and so on.
+//!
+//! ```
+//! # use stm32_i2s_v12x::driver::*;
+//! # use stm32_i2s_v12x::mock::*;
+//! let driver = I2sDriverConfig::new_master()
+//!     .data_format(DataFormat::Data16Channel32)
+//!     .prescaler(false, 4)
+//!     .i2s_driver(MockI2sPeripheral::<0>::new(8_192_000));
+//! assert_eq!(driver.sample_rate(), 16_000);
+//! ```
+use core::cell::Cell;
+
+use vcell::VolatileCell;
+
+use crate::{DualI2sPeripheral, I2sPeripheral, WsPin};
+
+/// Number of register blocks available to mock peripherals.
+pub const SLOTS: usize = 16;
+
+/// Registers of a SPI/I2S register block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    /// Control register 1, CR1.
+    Cr1,
+    /// Control register 2, CR2.
+    Cr2,
+    /// Status register, SR.
+    Sr,
+    /// Data register, DR.
+    Dr,
+    /// CRC polynomial register, CRCPR.
+    Crcpr,
+    /// RX CRC register, RXCRCR.
+    Rxcrcr,
+    /// TX CRC register, TXCRCR.
+    Txcrcr,
+    /// I2S configuration register, I2SCFGR.
+    I2scfgr,
+    /// I2S prescaler register, I2SPR.
+    I2spr,
+}
+
+impl Register {
+    /// Value of the register after a reset.
+    pub fn reset_value(self) -> u32 {
+        match self {
+            Register::Sr => 0x0002,
+            Register::Crcpr => 0x0007,
+            Register::I2spr => 0x000a,
+            _ => 0,
+        }
+    }
+}
+
+const REGISTERS: [Register; 9] = [
+    Register::Cr1,
+    Register::Cr2,
+    Register::Sr,
+    Register::Dr,
+    Register::Crcpr,
+    Register::Rxcrcr,
+    Register::Txcrcr,
+    Register::I2scfgr,
+    Register::I2spr,
+];
+
+/// In-memory SPI/I2S register block.
+#[repr(C)]
+pub struct MockRegisterBlock {
+    words: [VolatileCell<u32>; 9],
+}
+
+// Safety: a block is only accessed by the unique mock peripheral using its slot, and by the test
+// owning that peripheral.
+unsafe impl Sync for MockRegisterBlock {}
+
+impl MockRegisterBlock {
+    /// Read the raw content of a register.
+    pub fn read(&self, register: Register) -> u32 {
+        self.words[register as usize].get()
+    }
+
+    /// Write the raw content of a register.
+    pub fn write(&self, register: Register, value: u32) {
+        self.words[register as usize].set(value)
+    }
+
+    /// Set all registers to their reset value.
+    pub fn reset(&self) {
+        for register in REGISTERS {
+            self.write(register, register.reset_value());
+        }
+    }
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: VolatileCell<u32> = VolatileCell::new(0);
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_BLOCK: MockRegisterBlock = MockRegisterBlock { words: [ZERO; 9] };
+
+static REGISTER_BLOCKS: [MockRegisterBlock; SLOTS] = [EMPTY_BLOCK; SLOTS];
+
+/// Get the register block of a slot.
+///
+/// # Panics
+///
+/// This function panics if `slot` is not less than [`SLOTS`].
+pub fn register_block(slot: usize) -> &'static MockRegisterBlock {
+    &REGISTER_BLOCKS[slot]
+}
+
+/// WS pin whose level is set by the test.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MockWsPin {
+    high: bool,
+}
+
+impl MockWsPin {
+    /// Set the level of the pin. `true` means high level.
+    pub fn set_high(&mut self, high: bool) {
+        self.high = high;
+    }
+}
+
+impl WsPin for MockWsPin {
+    fn is_low(&self) -> bool {
+        !self.high
+    }
+    fn is_high(&self) -> bool {
+        self.high
+    }
+}
+
+/// WS pin following a scripted sequence of levels, `true` meaning high.
+///
+/// Each call to `is_low` or `is_high` reads the current level, then advances to the next one.
+/// Once the sequence is exhausted, the last level is kept. An empty sequence reads as low. This
+/// allows to test synchronisation of slave transfers, eg "low for 3 polls then high".
+#[derive(Debug, Clone)]
+pub struct ScriptedWsPin<'a> {
+    levels: &'a [bool],
+    pos: Cell<usize>,
+}
+
+impl<'a> ScriptedWsPin<'a> {
+    /// Create a pin following `levels`.
+    pub fn new(levels: &'a [bool]) -> Self {
+        Self {
+            levels,
+            pos: Cell::new(0),
+        }
+    }
+
+    /// Number of levels read so far.
+    pub fn reads(&self) -> usize {
+        self.pos.get()
+    }
+
+    fn next_level(&self) -> bool {
+        let pos = self.pos.get();
+        self.pos.set(pos + 1);
+        let last = self.levels.len().saturating_sub(1);
+        self.levels.get(pos.min(last)).copied().unwrap_or(false)
+    }
+}
+
+impl WsPin for ScriptedWsPin<'_> {
+    fn is_low(&self) -> bool {
+        !self.next_level()
+    }
+    fn is_high(&self) -> bool {
+        self.next_level()
+    }
+}
+
+/// Mock [`I2sPeripheral`] using the register block of slot `SLOT`, and `P` as WS pin.
+///
+/// Slots are picked by hand and nothing checks that they are distinct: two tests using the same
+/// slot share a register block and race under the parallel test runner, failing randomly or
+/// hanging, without any warning. Keep a list of used slots close to the tests.
+///
+/// The register block doesn't evolve on its own, so the transfer state machines only go as far
+/// as the test scripts status flags and WS levels. Behaviors depending on timing between flags,
+/// like an error raised in the middle of a frame, must be reproduced step by step.
+#[derive(Debug)]
+pub struct MockI2sPeripheral<const SLOT: usize, P = MockWsPin> {
+    i2s_freq: u32,
+    ws_pin: P,
+    rcc_resets: u32,
+}
+
+impl<const SLOT: usize> MockI2sPeripheral<SLOT> {
+    /// Create a mock peripheral with the given I2S clock frequency. The register block is reset.
+    pub fn new(i2s_freq: u32) -> Self {
+        Self::with_ws_pin(i2s_freq, Default::default())
+    }
+}
+
+impl<const SLOT: usize, P: WsPin> MockI2sPeripheral<SLOT, P> {
+    /// Create a mock peripheral with the given I2S clock frequency and WS pin, eg a
+    /// [`ScriptedWsPin`]. The register block is reset.
+    pub fn with_ws_pin(i2s_freq: u32, ws_pin: P) -> Self {
+        register_block(SLOT).reset();
+        Self {
+            i2s_freq,
+            ws_pin,
+            rcc_resets: 0,
+        }
+    }
+
+    /// Get the register block of the peripheral.
+    pub fn registers(&self) -> &'static MockRegisterBlock {
+        register_block(SLOT)
+    }
+
+    /// Number of times `rcc_reset` has been called.
+    pub fn rcc_reset_count(&self) -> u32 {
+        self.rcc_resets
+    }
+}
+
+unsafe impl<const SLOT: usize, P: WsPin> I2sPeripheral for MockI2sPeripheral<SLOT, P> {
+    type WsPin = P;
+    const REGISTERS: *const () = &REGISTER_BLOCKS[SLOT] as *const _ as *const ();
+    fn i2s_freq(&self) -> u32 {
+        self.i2s_freq
+    }
+    fn ws_pin(&self) -> &Self::WsPin {
+        &self.ws_pin
+    }
+    fn ws_pin_mut(&mut self) -> &mut Self::WsPin {
+        &mut self.ws_pin
+    }
+    fn rcc_reset(&mut self) {
+        self.rcc_resets += 1;
+        register_block(SLOT).reset();
+    }
+}
+
+/// Mock [`DualI2sPeripheral`] using the register blocks of slots `MAIN` and `EXT`.
+#[derive(Debug)]
+pub struct MockDualI2sPeripheral<const MAIN: usize, const EXT: usize> {
+    i2s_freq: u32,
+    ws_pin: MockWsPin,
+    rcc_resets: u32,
+}
+
+impl<const MAIN: usize, const EXT: usize> MockDualI2sPeripheral<MAIN, EXT> {
+    /// Create a mock peripheral with the given I2S clock frequency. The register blocks are reset.
+    pub fn new(i2s_freq: u32) -> Self {
+        register_block(MAIN).reset();
+        register_block(EXT).reset();
+        Self {
+            i2s_freq,
+            ws_pin: Default::default(),
+            rcc_resets: 0,
+        }
+    }
+
+    /// Get the register block of the main part.
+    pub fn main_registers(&self) -> &'static MockRegisterBlock {
+        register_block(MAIN)
+    }
+
+    /// Get the register block of the extension part.
+    pub fn ext_registers(&self) -> &'static MockRegisterBlock {
+        register_block(EXT)
+    }
+
+    /// Number of times `rcc_reset` has been called.
+    pub fn rcc_reset_count(&self) -> u32 {
+        self.rcc_resets
+    }
+}
+
+unsafe impl<const MAIN: usize, const EXT: usize> DualI2sPeripheral
+    for MockDualI2sPeripheral<MAIN, EXT>
+{
+    type WsPin = MockWsPin;
+    const MAIN_REGISTERS: *const () = &REGISTER_BLOCKS[MAIN] as *const _ as *const ();
+    const EXT_REGISTERS: *const () = &REGISTER_BLOCKS[EXT] as *const _ as *const ();
+    fn i2s_freq(&self) -> u32 {
+        self.i2s_freq
+    }
+    fn ws_pin(&self) -> &Self::WsPin {
+        &self.ws_pin
+    }
+    fn ws_pin_mut(&mut self) -> &mut Self::WsPin {
+        &mut self.ws_pin
+    }
+    fn rcc_reset(&mut self) {
+        self.rcc_resets += 1;
+        register_block(MAIN).reset();
+        register_block(EXT).reset();
+    }
+}
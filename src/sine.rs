@@ -0,0 +1,58 @@
+//! Sine wave tables computed at compile time, eg to generate test tones.
+//!
+//! Computations only use integer arithmetic, so tables can be built in a const context on any
+//! target:
+//! ```
+//! # use stm32_i2s_v12x::sine::sine_table;
+//! // Full scale sine wave spanning 32 samples. With a 48 kHz sampling rate this give a 1500 Hz
+//! // signal.
+//! const SINE_1500: [i16; 32] = sine_table();
+//! assert_eq!(SINE_1500[..9], [0, 6393, 12539, 18204, 23170, 27245, 30273, 32137, 32767]);
+//! assert_eq!((SINE_1500[16], SINE_1500[24]), (0, -32767));
+//! ```
+
+/// One in Q30 fixed point format.
+const ONE: i64 = 1 << 30;
+
+/// π/2 in Q30 fixed point format.
+const HALF_PI: i64 = 1_686_629_713;
+
+/// Sine of `x`, in Q30 fixed point format, for `x` from 0 to π/2.
+///
+/// This is a Taylor polynomial up to the 11th degree, evaluated with the Horner method. The error
+/// is far below the resolution of a 16 bit sample.
+const fn sin_q30(x: i64) -> i64 {
+    let x2 = (x * x) >> 30;
+    let mut acc = ONE;
+    let mut k = 11;
+    while k > 1 {
+        // 1 - x² / (k * (k - 1)) * acc
+        acc = ONE - ((x2 * acc) >> 30) / (k * (k - 1));
+        k -= 2;
+    }
+    (x * acc) >> 30
+}
+
+/// Get a full scale sine wave period spanning `N` samples, starting at 0 and rising.
+///
+/// Samples are rounded to the nearest integer. Peaks are ±32767, so the table can be negated
+/// without overflow. Playing it in a loop at
+/// sample rate `fs` gives a `fs / N` tone.
+pub const fn sine_table<const N: usize>() -> [i16; N] {
+    let mut table = [0; N];
+    let mut i = 0;
+    while i < N {
+        // phase of the sample, a full period being 4 * ONE
+        let phase = ((i as i64) * 4 * ONE) / N as i64;
+        let quadrant = phase / ONE;
+        let mut frac = phase % ONE;
+        if quadrant % 2 == 1 {
+            frac = ONE - frac;
+        }
+        let sin = sin_q30((frac * HALF_PI) >> 30);
+        let sample = ((sin * i16::MAX as i64) + (ONE / 2)) >> 30;
+        table[i] = if quadrant < 2 { sample } else { -sample } as i16;
+        i += 1;
+    }
+    table
+}
@@ -7,6 +7,11 @@
 //!
 //! At the moment, transfer is not implemented for 24-bit data.
 //!
+//! With `Data16Channel32`, a sample is a single half word in the data register, whatever the
+//! standard. Placing the 16 significant bits at the right end of the 32 bit channel, including
+//! for LSB justified standard, is done by the hardware, so audio frames don't depend on the
+//! justification.
+//!
 //! # Configure and instantiate transfer
 //!
 //! [`I2sTransferConfig`] is used to create configuration of the i2s transfer:
@@ -35,11 +40,7 @@
 //! ```ignore
 //! // Full scale sine wave spanning 32 samples. With a 48 kHz sampling rate this give a 1500 Hz
 //! // signal.
-//! const SINE_1500: [i16; 32] = [
-//!     0, 6392, 12539, 18204, 23169, 27244, 30272, 32137, 32767, 32137, 30272, 27244, 23169,
-//!     18204, 12539, 6392, 0, -6392, -12539, -18204, -23169, -27244, -30272, -32137, -32767,
-//!     -32137, -30272, -27244, -23169, -18204, -12539, -6392,
-//! ];
+//! const SINE_1500: [i16; 32] = stm32_i2s_v12x::sine::sine_table();
 //!
 //! // Iterator generating audio data for 1 sec (at 48 kHz sampling rate)
 //! let sine_1500_iter = SINE_1500.iter().map(|&x| (x, x)).cycle().take(48_000);
@@ -95,34 +96,47 @@
 use crate::sealed::Sealed;
 use core::convert::Infallible;
 use core::marker::PhantomData;
+use core::mem::size_of;
 
 use nb::Error::WouldBlock;
 
-use crate::driver::ClockPolarity;
 use crate::driver::I2sDriver as Driver;
 use crate::driver::I2sDriverConfig as DriverConfig;
+use crate::driver::{Channel, ClockPolarity};
 use crate::{I2sPeripheral, WsPin};
 
+use self::hooks::{ReceiveHooks, TransmitHooks};
+
 pub use crate::marker::{self, *};
 
 /// Trait to build an internal frame representation of an `I2sTransfer` from markers.
 #[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` isn't a standard and data format combination supported by `I2sTransfer`",
+    note = "`Data24Channel32` isn't supported by `I2sTransfer`"
+)]
 pub trait FrameFormat: Sealed {
     /// Raw frame representation for transfer implementation
     ///
     /// The actual type is always an array of u16
     type RawFrame: Default + Copy + Sync + Send + AsRef<[u16]> + AsMut<[u16]>;
+    /// Type of audio frames used by the API, eg `(i16, i16)`
+    type AudioFrame: Copy;
+    /// Number of half words of a single sample, 1 or 2
+    const SAMPLE_WORDS: usize;
 }
 
 /// Syntax sugar to get the appropriate internal frame representation from markers.
 type RawFrame<STD, FMT> = <(STD, FMT) as FrameFormat>::RawFrame;
 
 macro_rules! impl_frame_format{
-    ($(([$($std:ident),*],$fmt:ident,$raw_frame:ty)),*) => {
+    ($(([$($std:ident),*],$fmt:ident,$raw_frame:ty,$audio_frame:ty,$sample_words:expr)),*) => {
         $(
             $(
                 impl FrameFormat for ($std,$fmt) {
                     type RawFrame = $raw_frame;
+                    type AudioFrame = $audio_frame;
+                    const SAMPLE_WORDS: usize = $sample_words;
                 }
             )*
         )*
@@ -132,15 +146,78 @@ macro_rules! impl_frame_format{
 impl<T: Sealed, U: Sealed> Sealed for (T, U) {}
 
 impl_frame_format!(
-    ([Philips, Msb, Lsb], Data16Channel16, [u16; 2]),
-    ([Philips, Msb, Lsb], Data16Channel32, [u16; 2]),
-    ([Philips, Msb, Lsb], Data32Channel32, [u16; 4]),
-    ([PcmShortSync, PcmLongSync], Data16Channel16, [u16; 1]),
-    ([PcmShortSync, PcmLongSync], Data16Channel32, [u16; 1]),
-    ([PcmShortSync, PcmLongSync], Data32Channel32, [u16; 2])
+    (
+        [Philips, Msb, Lsb],
+        Data16Channel16,
+        [u16; 2],
+        (i16, i16),
+        1
+    ),
+    (
+        [Philips, Msb, Lsb],
+        Data16Channel32,
+        [u16; 2],
+        (i16, i16),
+        1
+    ),
+    (
+        [Philips, Msb, Lsb],
+        Data32Channel32,
+        [u16; 4],
+        (i32, i32),
+        2
+    ),
+    (
+        [PcmShortSync, PcmLongSync],
+        Data16Channel16,
+        [u16; 1],
+        i16,
+        1
+    ),
+    (
+        [PcmShortSync, PcmLongSync],
+        Data16Channel32,
+        [u16; 1],
+        i16,
+        1
+    ),
+    (
+        [PcmShortSync, PcmLongSync],
+        Data32Channel32,
+        [u16; 2],
+        i32,
+        2
+    )
 );
 
+/// Marker trait for data formats that can be used by `I2sTransfer` with the standard `STD`.
+///
+/// This is implemented for every data format `FMT` such that `(STD, FMT)` implements
+/// [`FrameFormat`], and is only a named bound for error messages. It's required when selecting the
+/// standard or the data format of an [`I2sTransferConfig`], so an unsupported combination is
+/// reported at that point. At the moment, `Data24Channel32` isn't transfer capable:
+/// ```compile_fail
+/// # use stm32_i2s_v12x::transfer::*;
+/// let config = I2sTransferConfig::new_master().data_format(Data24Channel32);
+/// ```
+pub trait TransferCapable<STD>: DataFormat + Sealed {}
+
+impl<STD, FMT> TransferCapable<STD> for FMT
+where
+    STD: I2sStandard,
+    FMT: DataFormat,
+    (STD, FMT): FrameFormat,
+{
+}
+
 /// Types written to `I2sTransfer`.
+///
+/// Audio frames are `(i16, i16)` or `(i32, i32)` tuples with Philips, Msb and Lsb standards. PCM
+/// standards are monophonic, so audio frames are plain `i16` or `i32` values with those.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be written to an `I2sTransfer` using `{STD}` and `{FMT}`",
+    note = "PCM frames are mono, use `i16` or `i32` rather than `(i16, i16)` or `(i32, i32)` with PCM standards"
+)]
 pub trait ToRawFrame<STD, FMT>
 where
     (STD, FMT): FrameFormat,
@@ -193,6 +270,13 @@ impl_to_raw_frame!(
 );
 
 /// Types read from `I2sTransfer`.
+///
+/// Audio frames are `(i16, i16)` or `(i32, i32)` tuples with Philips, Msb and Lsb standards. PCM
+/// standards are monophonic, so audio frames are plain `i16` or `i32` values with those.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be read from an `I2sTransfer` using `{STD}` and `{FMT}`",
+    note = "PCM frames are mono, use `i16` or `i32` rather than `(i16, i16)` or `(i32, i32)` with PCM standards"
+)]
 pub trait FromRawFrame<STD, FMT>
 where
     (STD, FMT): FrameFormat,
@@ -241,13 +325,371 @@ impl_from_raw_frame!(
     }
 );
 
+/// Audio frames that can be scaled by a gain, used to fade audio data.
+pub trait Gain: Copy {
+    /// Apply a gain in Q16 fixed-point format, that is `65536` for unity gain.
+    fn apply_gain(&self, gain: u32) -> Self;
+}
+
+impl Gain for i16 {
+    fn apply_gain(&self, gain: u32) -> Self {
+        ((*self as i64 * gain as i64) >> 16) as i16
+    }
+}
+
+impl Gain for i32 {
+    fn apply_gain(&self, gain: u32) -> Self {
+        ((*self as i64 * gain as i64) >> 16) as i32
+    }
+}
+
+impl<T: Gain> Gain for (T, T) {
+    fn apply_gain(&self, gain: u32) -> Self {
+        (self.0.apply_gain(gain), self.1.apply_gain(gain))
+    }
+}
+
+/// Audio frames that can be averaged, used for decimation.
+pub trait Average: Copy {
+    /// Accumulator type, wide enough to sum `u32::MAX` frames without overflow.
+    type Sum: Copy + Default;
+    /// Add this frame to `sum`.
+    fn accumulate(&self, sum: Self::Sum) -> Self::Sum;
+    /// Get the average of `count` accumulated frames.
+    fn average(sum: Self::Sum, count: u32) -> Self;
+}
+
+impl Average for i16 {
+    type Sum = i64;
+    fn accumulate(&self, sum: i64) -> i64 {
+        sum + *self as i64
+    }
+    fn average(sum: i64, count: u32) -> Self {
+        (sum / count as i64) as i16
+    }
+}
+
+impl Average for i32 {
+    type Sum = i64;
+    fn accumulate(&self, sum: i64) -> i64 {
+        sum + *self as i64
+    }
+    fn average(sum: i64, count: u32) -> Self {
+        (sum / count as i64) as i32
+    }
+}
+
+impl<T: Average> Average for (T, T) {
+    type Sum = (T::Sum, T::Sum);
+    fn accumulate(&self, sum: Self::Sum) -> Self::Sum {
+        (self.0.accumulate(sum.0), self.1.accumulate(sum.1))
+    }
+    fn average(sum: Self::Sum, count: u32) -> Self {
+        (T::average(sum.0, count), T::average(sum.1, count))
+    }
+}
+
+/// Gain, in Q16 format, of the frame at `index` in a sequence of `len` frames faded in over
+/// `fade_in` frames and faded out over `fade_out` frames. The first and last frames are silent.
+fn fade_gain(index: u32, len: u32, fade_in: u32, fade_out: u32) -> u32 {
+    const UNITY: u64 = 1 << 16;
+    let mut gain = UNITY;
+    if index < fade_in {
+        gain = gain.min(UNITY * index as u64 / fade_in as u64);
+    }
+    let remaining = len - 1 - index;
+    if remaining < fade_out {
+        gain = gain.min(UNITY * remaining as u64 / fade_out as u64);
+    }
+    gain as u32
+}
+
+/// Iterator over the raw half words of audio frames, see [`pack_iter`].
+pub struct PackIter<ITER, STD, FMT>
+where
+    (STD, FMT): FrameFormat,
+{
+    frames: ITER,
+    frame: RawFrame<STD, FMT>,
+    index: usize,
+}
+
+impl<ITER, STD, FMT> Iterator for PackIter<ITER, STD, FMT>
+where
+    (STD, FMT): FrameFormat,
+    ITER: Iterator,
+    ITER::Item: ToRawFrame<STD, FMT>,
+{
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        if self.index >= self.frame.as_ref().len() {
+            self.frame = self.frames.next()?.to_raw();
+            self.index = 0;
+        }
+        let word = self.frame.as_ref()[self.index];
+        self.index += 1;
+        Some(word)
+    }
+}
+
+/// Convert audio frames into the half words stream expected by the data register.
+///
+/// Half words are produced in the order they must be written to the data register for the given
+/// standard and data format, so the result can be collected into a DMA buffer:
+/// ```
+/// # use stm32_i2s_v12x::transfer::*;
+/// let frames = [(1i32, -1i32)];
+/// let mut buf = [0u16; 4];
+/// for (w, word) in buf.iter_mut().zip(pack_iter::<Philips, Data32Channel32, _>(frames)) {
+///     *w = word;
+/// }
+/// assert_eq!(buf, [0x0000, 0x0001, 0xFFFF, 0xFFFF]);
+/// ```
+pub fn pack_iter<STD, FMT, ITER>(frames: ITER) -> PackIter<ITER::IntoIter, STD, FMT>
+where
+    (STD, FMT): FrameFormat,
+    ITER: IntoIterator,
+    ITER::Item: ToRawFrame<STD, FMT>,
+{
+    let frame = RawFrame::<STD, FMT>::default();
+    let index = frame.as_ref().len();
+    PackIter {
+        frames: frames.into_iter(),
+        frame,
+        index,
+    }
+}
+
+/// Iterator over audio frames rebuilt from raw half words, see [`unpack_iter`].
+pub struct UnpackIter<ITER, STD, FMT, T> {
+    words: ITER,
+    _std: PhantomData<STD>,
+    _fmt: PhantomData<FMT>,
+    _t: PhantomData<T>,
+}
+
+impl<ITER, STD, FMT, T> Iterator for UnpackIter<ITER, STD, FMT, T>
+where
+    (STD, FMT): FrameFormat,
+    ITER: Iterator<Item = u16>,
+    T: FromRawFrame<STD, FMT>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let mut frame = RawFrame::<STD, FMT>::default();
+        for word in frame.as_mut() {
+            *word = self.words.next()?;
+        }
+        Some(T::from_raw(frame))
+    }
+}
+
+/// Rebuild audio frames from half words read from the data register, typically a DMA buffer.
+///
+/// This is the reverse of [`pack_iter`]. The first half word must be the start of a frame.
+/// Remaining half words that don't form a complete frame are ignored.
+/// ```
+/// # use stm32_i2s_v12x::transfer::*;
+/// let words = [0x0000, 0x0001, 0xFFFF, 0xFFFF, 0x1234];
+/// let mut frames = unpack_iter::<Philips, Data32Channel32, _, (i32, i32)>(words);
+/// assert_eq!(frames.next(), Some((1, -1)));
+/// assert_eq!(frames.next(), None);
+/// ```
+pub fn unpack_iter<STD, FMT, ITER, T>(words: ITER) -> UnpackIter<ITER::IntoIter, STD, FMT, T>
+where
+    (STD, FMT): FrameFormat,
+    ITER: IntoIterator<Item = u16>,
+    T: FromRawFrame<STD, FMT>,
+{
+    UnpackIter {
+        words: words.into_iter(),
+        _std: PhantomData,
+        _fmt: PhantomData,
+        _t: PhantomData,
+    }
+}
+
+/// Ping-pong buffer of audio frames, for double buffering without DMA.
+///
+/// The transfer side uses the active half through the non-blocking `read` or `write` of a
+/// transfer, while the user fills or processes the other half. Once the active half is done,
+/// `swap` exchanges both halves.
+/// ```
+/// # use core::convert::Infallible;
+/// # use stm32_i2s_v12x::transfer::DoubleBuffer;
+/// let (mut first, mut second) = ([0i16; 2], [0i16; 2]);
+/// let mut buffer = DoubleBuffer::new(&mut first, &mut second);
+/// let mut samples = 1..;
+/// // stands for `transfer.read()`
+/// let mut read = || -> nb::Result<i16, Infallible> { Ok(samples.next().unwrap()) };
+/// assert_eq!(buffer.read_into_active(&mut read), Err(nb::Error::WouldBlock));
+/// assert_eq!(buffer.read_into_active(&mut read), Ok(()));
+/// buffer.swap();
+/// assert_eq!(buffer.inactive(), [1, 2]);
+/// ```
+#[derive(Debug)]
+pub struct DoubleBuffer<'a, T> {
+    halves: [&'a mut [T]; 2],
+    active: usize,
+    pos: usize,
+}
+
+impl<'a, T: Copy> DoubleBuffer<'a, T> {
+    /// Create a double buffer, `first` being the active half.
+    pub fn new(first: &'a mut [T], second: &'a mut [T]) -> Self {
+        Self {
+            halves: [first, second],
+            active: 0,
+            pos: 0,
+        }
+    }
+
+    /// Get the half available to the user.
+    pub fn inactive(&mut self) -> &mut [T] {
+        self.halves[self.active ^ 1]
+    }
+
+    /// Return `true` if the active half has been completely read or written.
+    pub fn is_active_done(&self) -> bool {
+        self.pos >= self.halves[self.active].len()
+    }
+
+    /// Exchange both halves, and restart at the beginning of the new active half.
+    pub fn swap(&mut self) {
+        self.active ^= 1;
+        self.pos = 0;
+    }
+
+    /// Store a frame returned by `read` in the active half, typically a closure calling the
+    /// `read` method of a transfer.
+    ///
+    /// This returns `Ok` once the active half is full, without calling `read` anymore until the
+    /// next `swap`, `WouldBlock` if more frames are needed, and forwards errors of `read`.
+    pub fn read_into_active<F, E>(&mut self, mut read: F) -> nb::Result<(), E>
+    where
+        F: FnMut() -> nb::Result<T, E>,
+    {
+        if self.is_active_done() {
+            return Ok(());
+        }
+        self.halves[self.active][self.pos] = read()?;
+        self.pos += 1;
+        if self.is_active_done() {
+            Ok(())
+        } else {
+            Err(WouldBlock)
+        }
+    }
+
+    /// Give the next frame of the active half to `write`, typically a closure calling the `write`
+    /// method of a transfer.
+    ///
+    /// This returns `Ok` once the whole active half is written, without calling `write` anymore
+    /// until the next `swap`, `WouldBlock` if frames remain, and forwards errors of `write`.
+    pub fn write_from_active<F, E>(&mut self, mut write: F) -> nb::Result<(), E>
+    where
+        F: FnMut(T) -> nb::Result<(), E>,
+    {
+        if self.is_active_done() {
+            return Ok(());
+        }
+        write(self.halves[self.active][self.pos])?;
+        self.pos += 1;
+        if self.is_active_done() {
+            Ok(())
+        } else {
+            Err(WouldBlock)
+        }
+    }
+}
+
 /// Errors that may require a special handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum I2sTransferError {
+    /// Received data was lost because the data register wasn't read in time.
+    Overrun,
+    /// A slave detected a WS level change at an unexpected time.
+    FrameError,
+    /// A slave had to transmit before data was written to the data register.
+    Underrun,
+}
+
+/// Outcome of a slave receive `read_status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadOutcome<T> {
+    /// A complete audio frame has been received.
+    Frame(T),
+    /// No complete frame yet.
+    Pending,
+    /// A frame error or an overrun was detected and the transfer is resynchronising. The
+    /// partially received frame is lost.
+    Recovered,
+}
+
+/// Errors returned by `try_read_while`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ReadWhileError<E> {
+    /// An overrun occurred in master mode, see [`I2sTransferError::Overrun`].
     Overrun,
+    /// A slave gave up synchronising, see [`I2sTransferConfig::max_sync_attempts`].
+    SyncFailed,
+    /// The predicate returned an error.
+    Predicate(E),
+}
+
+/// Data used to re-prime the data register when a slave transmit transfer resynchronises after an
+/// underrun or a frame error.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum UnderflowPolicy {
+    /// Restart with a silent frame.
+    Zero,
+    /// Restart by repeating the last frame that was written.
+    #[default]
+    HoldLast,
+}
+
+/// Settings that only matter to `I2sTransfer`, not to the underlying driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct TransferOptions {
+    underflow_policy: UnderflowPolicy,
+    sync_confirm_polls: u8,
+    clip_detect: bool,
+    clip_frames: u8,
+    verify_ws_alignment: bool,
+    max_sync_attempts: Option<u32>,
+    detect_gaps: bool,
+    histogram: bool,
+}
+
+impl Default for TransferOptions {
+    fn default() -> Self {
+        Self {
+            underflow_policy: Default::default(),
+            sync_confirm_polls: 2,
+            clip_detect: false,
+            clip_frames: CLIP_FRAMES,
+            verify_ws_alignment: false,
+            max_sync_attempts: None,
+            detect_gaps: false,
+            histogram: false,
+        }
+    }
+}
+
+impl TransferOptions {
+    fn sync_attempts_exhausted(&self, failures: u32) -> bool {
+        match self.max_sync_attempts {
+            Some(max) => failures >= max.max(1),
+            None => false,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// [`I2sTransfer`] configuration.
 ///
 ///  - `MS`: `Master` or `Slave`
@@ -259,6 +701,7 @@ pub enum I2sTransferError {
 /// return a new object instead.
 pub struct I2sTransferConfig<MS, DIR, STD, FMT> {
     driver_config: DriverConfig<MS, DIR, STD>,
+    options: TransferOptions,
     _fmt: PhantomData<FMT>,
 }
 
@@ -267,6 +710,7 @@ impl I2sTransferConfig<Slave, Transmit, Philips, Data16Channel16> {
     pub fn new_slave() -> Self {
         Self {
             driver_config: DriverConfig::new_slave(),
+            options: Default::default(),
             _fmt: PhantomData,
         }
     }
@@ -277,6 +721,7 @@ impl I2sTransferConfig<Master, Transmit, Philips, Data16Channel16> {
     pub fn new_master() -> Self {
         Self {
             driver_config: DriverConfig::new_master(),
+            options: Default::default(),
             _fmt: PhantomData,
         }
     }
@@ -298,13 +743,7 @@ where
         i2s_peripheral: I,
     ) -> I2sTransfer<I, MS, DIR, STD, FMT> {
         let driver = self.driver_config.i2s_driver(i2s_peripheral);
-        I2sTransfer::<I, MS, DIR, STD, FMT> {
-            driver,
-            frame: Default::default(),
-            transfer_count: 0,
-            sync: false,
-            _fmt: PhantomData,
-        }
+        I2sTransfer::<I, MS, DIR, STD, FMT>::_from_parts(driver, self.options, None)
     }
 }
 
@@ -320,6 +759,7 @@ impl<MS, DIR, STD, FMT> I2sTransferConfig<MS, DIR, STD, FMT> {
     pub fn transmit(self) -> I2sTransferConfig<MS, Transmit, STD, FMT> {
         I2sTransferConfig::<MS, Transmit, STD, FMT> {
             driver_config: self.driver_config.transmit(),
+            options: self.options,
             _fmt: PhantomData,
         }
     }
@@ -327,6 +767,7 @@ impl<MS, DIR, STD, FMT> I2sTransferConfig<MS, DIR, STD, FMT> {
     pub fn receive(self) -> I2sTransferConfig<MS, Receive, STD, FMT> {
         I2sTransferConfig::<MS, Receive, STD, FMT> {
             driver_config: self.driver_config.receive(),
+            options: self.options,
             _fmt: PhantomData,
         }
     }
@@ -335,9 +776,11 @@ impl<MS, DIR, STD, FMT> I2sTransferConfig<MS, DIR, STD, FMT> {
     pub fn standard<NEW_STD>(self, _standard: NEW_STD) -> I2sTransferConfig<MS, DIR, NEW_STD, FMT>
     where
         NEW_STD: marker::I2sStandard,
+        FMT: TransferCapable<NEW_STD>,
     {
         I2sTransferConfig::<MS, DIR, NEW_STD, FMT> {
             driver_config: self.driver_config.standard(_standard),
+            options: self.options,
             _fmt: PhantomData,
         }
     }
@@ -345,18 +788,21 @@ impl<MS, DIR, STD, FMT> I2sTransferConfig<MS, DIR, STD, FMT> {
     pub fn clock_polarity(self, polarity: ClockPolarity) -> Self {
         I2sTransferConfig::<MS, DIR, STD, FMT> {
             driver_config: self.driver_config.clock_polarity(polarity),
+            options: self.options,
             _fmt: PhantomData,
         }
     }
 
-    /// Select data format. The parameter is just a marker implementing [`DataFormat`].
+    /// Select data format. The parameter is just a marker implementing [`DataFormat`] that must be
+    /// [`TransferCapable`] with the selected standard.
     #[allow(non_camel_case_types)]
     pub fn data_format<NEW_FMT>(self, _format: NEW_FMT) -> I2sTransferConfig<MS, DIR, STD, NEW_FMT>
     where
-        NEW_FMT: marker::DataFormat,
+        NEW_FMT: TransferCapable<STD>,
     {
         I2sTransferConfig::<MS, DIR, STD, NEW_FMT> {
             driver_config: self.driver_config.data_format(NEW_FMT::VALUE),
+            options: self.options,
             _fmt: PhantomData,
         }
     }
@@ -367,6 +813,7 @@ impl<MS, DIR, STD, FMT> I2sTransferConfig<MS, DIR, STD, FMT> {
     pub fn to_slave(self) -> I2sTransferConfig<Slave, DIR, STD, FMT> {
         I2sTransferConfig::<Slave, DIR, STD, FMT> {
             driver_config: self.driver_config.to_slave(),
+            options: self.options,
             _fmt: PhantomData,
         }
     }
@@ -375,6 +822,7 @@ impl<MS, DIR, STD, FMT> I2sTransferConfig<MS, DIR, STD, FMT> {
     pub fn to_master(self) -> I2sTransferConfig<Master, DIR, STD, FMT> {
         I2sTransferConfig::<Master, DIR, STD, FMT> {
             driver_config: self.driver_config.to_master(),
+            options: self.options,
             _fmt: PhantomData,
         }
     }
@@ -389,6 +837,7 @@ impl<DIR, STD, FMT> I2sTransferConfig<Master, DIR, STD, FMT> {
     pub fn master_clock(self, enable: bool) -> Self {
         I2sTransferConfig::<Master, DIR, STD, FMT> {
             driver_config: self.driver_config.master_clock(enable),
+            options: self.options,
             _fmt: PhantomData,
         }
     }
@@ -411,6 +860,7 @@ impl<DIR, STD, FMT> I2sTransferConfig<Master, DIR, STD, FMT> {
     pub fn prescaler(self, odd: bool, div: u8) -> Self {
         I2sTransferConfig::<Master, DIR, STD, FMT> {
             driver_config: self.driver_config.prescaler(odd, div),
+            options: self.options,
             _fmt: PhantomData,
         }
     }
@@ -419,6 +869,7 @@ impl<DIR, STD, FMT> I2sTransferConfig<Master, DIR, STD, FMT> {
     pub fn request_frequency(self, freq: u32) -> Self {
         I2sTransferConfig::<Master, DIR, STD, FMT> {
             driver_config: self.driver_config.request_frequency(freq),
+            options: self.options,
             _fmt: PhantomData,
         }
     }
@@ -429,11 +880,117 @@ impl<DIR, STD, FMT> I2sTransferConfig<Master, DIR, STD, FMT> {
     pub fn require_frequency(self, freq: u32) -> Self {
         I2sTransferConfig::<Master, DIR, STD, FMT> {
             driver_config: self.driver_config.require_frequency(freq),
+            options: self.options,
             _fmt: PhantomData,
         }
     }
 }
 
+impl<DIR, STD, FMT> I2sTransferConfig<Slave, DIR, STD, FMT> {
+    /// Set how many consecutive reads of the WS line at a stable level are required to
+    /// synchronise with the bus.
+    ///
+    /// The last read happens after activating the peripheral, others before. More reads harden
+    /// synchronisation against glitches, for example on long cables. The default is 2, and 0 is
+    /// handled like 1.
+    ///
+    /// This applies to Slave mode only.
+    pub fn sync_confirm_polls(mut self, polls: u8) -> Self {
+        self.options.sync_confirm_polls = polls;
+        self
+    }
+
+    /// Bound the number of failed synchronisation attempts of blocking methods.
+    ///
+    /// An attempt fails when the WS line changes during the confirmation. After `attempts`
+    /// consecutive failures, `read_while` and `write_iter` (and methods built on them) give up and
+    /// return, and [`sync_failed`](I2sTransfer::sync_failed) reports it. By default, there is no
+    /// limit and those methods try to synchronise forever. 0 is handled like 1.
+    ///
+    /// This applies to Slave mode only.
+    pub fn max_sync_attempts(mut self, attempts: u32) -> Self {
+        self.options.max_sync_attempts = Some(attempts);
+        self
+    }
+}
+
+impl<MS, STD, FMT> I2sTransferConfig<MS, Receive, STD, FMT> {
+    /// Enable or disable clipping detection, disabled by default.
+    ///
+    /// When enabled, received frames are checked against the full scale values of the data
+    /// format, and [`was_clipping`](I2sTransfer::was_clipping) reports when a sample was at full
+    /// scale for [`clip_frames`](Self::clip_frames) consecutive frames. This is meant to give
+    /// feedback to an automatic gain control, and adds a comparison per sample to the read path.
+    ///
+    /// This applies to Receive mode only.
+    pub fn clip_detect(mut self, enabled: bool) -> Self {
+        self.options.clip_detect = enabled;
+        self
+    }
+
+    /// Set the number of consecutive frames with a full scale sample for the clipping detection
+    /// to trigger, [`CLIP_FRAMES`] by default. A value of 0 is treated as 1.
+    ///
+    /// This applies to Receive mode only.
+    pub fn clip_frames(mut self, frames: u8) -> Self {
+        self.options.clip_frames = frames;
+        self
+    }
+
+    /// Enable or disable the estimation of frames lost by error recoveries, disabled by default.
+    ///
+    /// When enabled, overrun recoveries in master mode and resynchronisations in slave mode are
+    /// accounted, see [`gap_frames_estimate`](I2sTransfer::gap_frames_estimate).
+    ///
+    /// This applies to Receive mode only.
+    pub fn detect_gaps(mut self, enabled: bool) -> Self {
+        self.options.detect_gaps = enabled;
+        self
+    }
+
+    /// Enable or disable the amplitude histogram of received samples, disabled by default.
+    ///
+    /// When enabled, each received sample is accounted in a coarse histogram, see
+    /// [`histogram`](I2sTransfer::histogram). This is meant to check signal levels during
+    /// bring-up, and adds a few operations per sample to the read path.
+    ///
+    /// This applies to Receive mode only.
+    pub fn collect_histogram(mut self, enabled: bool) -> Self {
+        self.options.histogram = enabled;
+        self
+    }
+}
+
+impl<STD, FMT> I2sTransferConfig<Master, Receive, STD, FMT> {
+    /// Enable or disable the verification of frame alignment against the WS line, disabled by
+    /// default.
+    ///
+    /// No error flag can detect a frame slip in master receive mode. When enabled, the level of
+    /// the WS line is checked each time a frame is completed, and a mismatch is reported by
+    /// [`ws_misaligned`](I2sTransfer::ws_misaligned). This requires a bit more work per frame and
+    /// is not done with PCM standards, since the frame synchronisation pulse is too short to be
+    /// sampled.
+    ///
+    /// This applies to Master Receive mode only.
+    pub fn verify_ws_alignment(mut self, enabled: bool) -> Self {
+        self.options.verify_ws_alignment = enabled;
+        self
+    }
+}
+
+impl<STD, FMT> I2sTransferConfig<Slave, Transmit, STD, FMT> {
+    /// Select what is transmitted when the transfer resynchronises after an underrun.
+    ///
+    /// This only affects [`write`](I2sTransfer::write), since `write_iter` always restarts with
+    /// the next frame of its iterator. The default is [`UnderflowPolicy::HoldLast`].
+    ///
+    /// This applies to Slave Transmit mode only.
+    pub fn underflow_policy(mut self, policy: UnderflowPolicy) -> Self {
+        self.options.underflow_policy = policy;
+        self
+    }
+}
+
 /// Abstraction allowing sending and receiving of I2S data while erasing hardware details.
 ///
 /// This type is meant to implement the upcoming embeded-hal I2S trait.
@@ -461,63 +1018,403 @@ where
     frame: RawFrame<STD, FMT>,
     transfer_count: u8, //track part of the frame we transmitting
     sync: bool,
+    frame_count: u64,
+    clip_run: u8,
+    clipping: bool,
+    ws_misaligned: bool,
+    sync_failures: u32,
+    gap_frames: u32,
+    histogram: [[u32; HISTOGRAM_BUCKETS]; 2],
+    last_chside: bool,
+    preloaded: bool,
+    on_resync: Option<fn()>,
+    options: TransferOptions,
     _fmt: PhantomData<FMT>,
 }
 
 impl<I, MS, DIR, STD, FMT> I2sTransfer<I, MS, DIR, STD, FMT>
 where
     I: I2sPeripheral,
-    STD: I2sStandard,
     (STD, FMT): FrameFormat,
 {
-    /// When `true`, the level on WS line is correct for the peripheral to start operating.
+    /// Number of half words written to or read from the data register for one audio frame.
+    pub const FRAME_WORDS: usize = size_of::<RawFrame<STD, FMT>>() / size_of::<u16>();
+
+    // transfer_count is a u8, so a frame can't have more than 255 half words. This is evaluated
+    // when the transfer is instantiated by `_from_parts`, so a too large frame format fails to
+    // compile.
+    const _FRAME_WORDS_FIT_U8: () = assert!(
+        Self::FRAME_WORDS <= u8::MAX as usize,
+        "frame too large for transfer_count"
+    );
+
+    /// Create a transfer over `driver` with a fresh internal state. Every constructor and
+    /// conversion goes through this, so per-transfer counters and flags are reset in one place.
+    fn _from_parts(
+        driver: Driver<I, MS, DIR, STD>,
+        options: TransferOptions,
+        on_resync: Option<fn()>,
+    ) -> Self {
+        let () = Self::_FRAME_WORDS_FIT_U8;
+        Self {
+            driver,
+            frame: Default::default(),
+            transfer_count: 0,
+            sync: false,
+            frame_count: 0,
+            clip_run: 0,
+            clipping: false,
+            ws_misaligned: false,
+            sync_failures: 0,
+            gap_frames: 0,
+            histogram: Default::default(),
+            last_chside: false,
+            preloaded: false,
+            on_resync,
+            options,
+            _fmt: PhantomData,
+        }
+    }
+
+    /// Get the number of half words written to or read from the data register for one audio
+    /// frame. This is always equal to [`FRAME_WORDS`](Self::FRAME_WORDS), for code that only
+    /// has a transfer object at hand.
+    pub fn frame_words(&self) -> usize {
+        self.frame.as_ref().len()
+    }
+
+    /// Get an upper bound of the latency, in whole frames, added by the hardware between the data
+    /// register and the bus.
     ///
-    /// The peripheral must be enabled before this level is set.
+    /// The peripheral has no FIFO: there is a single half word buffer in front of the shift
+    /// register, so at most two half words are in flight. Rounded up to whole frames, this is 1
+    /// frame for all formats except 16 bit PCM, where a frame is a single half word and the
+    /// latency is 2 frames. With 32 bit formats, the two half words are only half a frame. This is
+    /// a bound for real-time budgets, not the minimum latency, and software buffering is not
+    /// included.
+    pub fn latency_frames(&self) -> u32 {
+        const WORDS_IN_FLIGHT: usize = 2;
+        WORDS_IN_FLIGHT.div_ceil(Self::FRAME_WORDS) as u32
+    }
+
+    /// Get the in-memory size in bytes of an audio frame, eg 4 for `(i16, i16)` or 8 for
+    /// `(i32, i32)`.
+    ///
+    /// This helps to size buffers when audio frames are stored as bytes.
+    pub fn sample_bytes(&self) -> usize {
+        size_of::<<(STD, FMT) as FrameFormat>::AudioFrame>()
+    }
+
+    /// Get the number of whole audio frames fitting in `bytes` bytes of memory.
+    ///
+    /// This helps to size storage buffers to a frame boundary, so no frame is split at the end of
+    /// a buffer. See [`sample_bytes`](Self::sample_bytes).
+    pub fn frames_in_bytes(&self, bytes: usize) -> usize {
+        bytes / self.sample_bytes()
+    }
+
+    /// Get a view of the internal raw frame, for debugging purposes.
+    ///
+    /// The content is only meaningful relative to the part of the frame that has been
+    /// transferred so far: during a partial transfer, the end of the buffer still contains data
+    /// from the previous frame.
+    pub fn current_raw_frame(&self) -> &[u16] {
+        self.frame.as_ref()
+    }
+
+    /// Get the number of audio frames transmitted or received since the transfer was started.
+    ///
+    /// Frames are counted when they are accepted by a write method or returned by a read method. A
+    /// frame staged by `preload` is counted when the interface is activated.
+    /// The counter saturates at `u64::MAX` and is reset by `end`, including when `end` is called
+    /// on a master receive overrun.
+    pub fn frame_counter(&self) -> u64 {
+        self.frame_count
+    }
+
     #[inline]
-    fn _ws_is_start(&self) -> bool {
-        match STD::WS_START_LEVEL {
-            false => self.driver.ws_pin().is_low(),
-            true => self.driver.ws_pin().is_high(),
+    fn _count_frame(&mut self) {
+        self.frame_count = self.frame_count.saturating_add(1);
+    }
+
+    /// Activate the I2s interface, counting the frame staged by `preload` since it starts going
+    /// out.
+    #[inline]
+    fn _activate(&mut self) {
+        if self.preloaded {
+            self.preloaded = false;
+            self._count_frame();
+        }
+        self.driver.enable();
+    }
+
+    #[inline]
+    fn _notify_resync(&self) {
+        if let Some(on_resync) = self.on_resync {
+            on_resync();
         }
     }
+
+    /// Clear the internal frame buffer and restart frame assembly from the beginning of a frame.
+    ///
+    /// Unlike `end`, neither the peripheral nor the synchronisation state are touched, so this is
+    /// only useful when alignment with the bus is already known to be correct, for example after
+    /// an external check. The next data register access is treated as the start of a frame.
+    pub fn reset_frame_assembly(&mut self) {
+        self.frame = Default::default();
+        self.transfer_count = 0;
+    }
+
+    /// Treat the next data register access as the start of a frame, for streams framed by an
+    /// external signal.
+    ///
+    /// This is meant to be called when an external synchronisation signal, e.g. a GPIO
+    /// interrupt, reports a frame start. Unlike `reset_frame_assembly`, the internal frame buffer
+    /// is kept as is. Calling this at a wrong time shifts all following frames, and with stereo
+    /// standards, swaps channels.
+    pub fn realign_to_frame_start(&mut self) {
+        self.transfer_count = 0;
+    }
 }
 
-/// Constructors and Destructors
 impl<I, MS, DIR, STD, FMT> I2sTransfer<I, MS, DIR, STD, FMT>
 where
     I: I2sPeripheral,
     STD: I2sStandard,
-    FMT: DataFormat,
     (STD, FMT): FrameFormat,
 {
-    /// Instantiate and configure an i2s driver around an [`I2sPeripheral`].
-    ///
-    /// # Panics
+    /// When `true`, the level on WS line is correct for the peripheral to start operating.
     ///
-    /// This method panics if an exact frequency is required by the config and that frequency
-    /// cannot be set.
-    pub fn new(i2s_peripheral: I, config: I2sTransferConfig<MS, DIR, STD, FMT>) -> Self {
-        config.i2s_transfer(i2s_peripheral)
+    /// The peripheral must be enabled before this level is set.
+    #[inline]
+    fn _ws_is_start(&self) -> bool {
+        match STD::WS_START_LEVEL {
+            false => self.driver.ws_pin().is_low(),
+            true => self.driver.ws_pin().is_high(),
+        }
     }
 
-    /// Destroy the transfer, release the owned i2s device, and reset its configuration.
-    pub fn release(self) -> I {
-        self.driver.release()
+    /// When `true`, the level on WS line allows starting the synchronisation process: it isn't the
+    /// start level for `sync_confirm_polls - 1` consecutive reads.
+    fn _ws_is_sync_ready(&self) -> bool {
+        let polls = self.options.sync_confirm_polls.saturating_sub(1);
+        (0..polls).all(|_| !self._ws_is_start())
+    }
+
+    /// Record a failed synchronisation attempt of a blocking method. Return `true` if the
+    /// method must give up. The count is reset each time the transfer synchronises.
+    fn _sync_attempt_failed(&mut self) -> bool {
+        self.sync_failures = self.sync_failures.saturating_add(1);
+        self.options.sync_attempts_exhausted(self.sync_failures)
     }
 }
 
 impl<I, MS, DIR, STD, FMT> I2sTransfer<I, MS, DIR, STD, FMT>
 where
     I: I2sPeripheral,
+    MS: Role,
     (STD, FMT): FrameFormat,
 {
-    /// Activate the I2s interface.
-    pub fn begin(&mut self) {
-        self.driver.enable()
+    /// Return `true` if the transfer is in master mode, for generic code over `MS`.
+    pub fn is_master(&self) -> bool {
+        MS::IS_MASTER
+    }
+
+    /// Change the direction of the peripheral and reset the internal state, activating the
+    /// interface again in master receive mode.
+    #[allow(non_camel_case_types)]
+    fn _turnaround<NEW_DIR: Direction>(
+        self,
+        direction: NEW_DIR,
+    ) -> I2sTransfer<I, MS, NEW_DIR, STD, FMT> {
+        let mut driver = self.driver.change_direction(direction);
+        if MS::IS_MASTER && NEW_DIR::VALUE == crate::driver::TransmitOrReceive::Receive {
+            driver.enable();
+        }
+        I2sTransfer::<I, MS, NEW_DIR, STD, FMT>::_from_parts(driver, self.options, self.on_resync)
     }
 }
 
-impl<I, DIR, STD, FMT> I2sTransfer<I, Slave, DIR, STD, FMT>
+impl<I, MS, DIR, STD, FMT> I2sTransfer<I, MS, DIR, STD, FMT>
+where
+    I: I2sPeripheral,
+    STD: I2sStandard,
+    FMT: DataFormat,
+    (STD, FMT): FrameFormat,
+{
+    /// Get the standard of the transfer as a runtime value, for generic code over `STD`.
+    pub fn standard_value(&self) -> crate::driver::I2sStandard {
+        STD::VALUE
+    }
+
+    /// Get the data format of the transfer as a runtime value, for generic code over `FMT`.
+    pub fn format_value(&self) -> crate::driver::DataFormat {
+        FMT::VALUE
+    }
+
+    /// Get the number of significant bits of a sample, that is 16, 24 or 32, whatever the
+    /// channel length.
+    ///
+    /// This is the resolution to use when normalising samples, eg to convert them to floats. See
+    /// also [`I2sDriver::data_bits`](Driver::data_bits).
+    pub fn significant_bits(&self) -> u8 {
+        use crate::driver::DataFormat::*;
+        match FMT::VALUE {
+            Data16Channel16 | Data16Channel32 => 16,
+            Data24Channel32 => 24,
+            Data32Channel32 => 32,
+        }
+    }
+
+    /// Get the number of bit clock periods in an audio frame, that is the channel length times
+    /// the number of channels.
+    ///
+    /// For example this is 64 for `Philips` with `Data16Channel32`, and 32 for PCM with the same
+    /// format. Unlike [`frame_words`](Self::frame_words), this matches what a logic analyzer
+    /// shows on the bus.
+    pub fn wire_bits_per_frame(&self) -> u32 {
+        // without master clock, the frequency coefficient is the number of bits per frame
+        crate::driver::_coef(false, STD::VALUE, FMT::VALUE)
+    }
+}
+
+impl<I, MS, DIR, FMT> I2sTransfer<I, MS, DIR, PcmLongSync, FMT>
+where
+    I: I2sPeripheral,
+    (PcmLongSync, FMT): FrameFormat,
+{
+    /// For pseudo stereo PCM, where two channels are multiplexed in consecutive frames, return
+    /// `true` if the last transferred frame is the first one of a pair.
+    ///
+    /// This is the parity of [`frame_counter`](Self::frame_counter), not a measure of WS timing:
+    /// the hardware has no notion of such pairs, all PCM frames look the same on the bus, so this
+    /// is application defined. Pairs are counted from the first frame transferred after the start
+    /// of the transfer. The counter is reset by `end`, including on a master receive overrun, and
+    /// frames lost by a slave resynchronisation aren't counted, so after any error recovery the
+    /// pair assignment may flip without notice. The application has to realign on its own, e.g.
+    /// with a marker in the data. This returns `false` before any frame is transferred.
+    pub fn frame_parity(&self) -> bool {
+        self.frame_count % 2 == 1
+    }
+}
+
+/// Constructors and Destructors
+impl<I, MS, DIR, STD, FMT> I2sTransfer<I, MS, DIR, STD, FMT>
+where
+    I: I2sPeripheral,
+    STD: I2sStandard,
+    FMT: DataFormat,
+    (STD, FMT): FrameFormat,
+{
+    /// Instantiate and configure an i2s driver around an [`I2sPeripheral`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics if an exact frequency is required by the config and that frequency
+    /// cannot be set.
+    pub fn new(i2s_peripheral: I, config: I2sTransferConfig<MS, DIR, STD, FMT>) -> Self {
+        config.i2s_transfer(i2s_peripheral)
+    }
+
+    /// Destroy the transfer, release the owned i2s device, and reset its configuration.
+    pub fn release(self) -> I {
+        self.driver.release()
+    }
+
+    /// Consume the transfer and return the underlying driver, for fine control of the peripheral.
+    ///
+    /// The I2s interface is deactivated and the internal state of the transfer is dropped, but
+    /// the configuration of the peripheral is kept. [`I2sDriver::into_transfer`](Driver::into_transfer)
+    /// does the reverse conversion.
+    pub fn into_driver(mut self) -> Driver<I, MS, DIR, STD> {
+        self.driver.disable();
+        self.driver
+    }
+
+    /// Consume the transfer and create a new one using another data format. The parameter is
+    /// just a marker implementing [`DataFormat`].
+    ///
+    /// This is cheaper than releasing and rebuilding the transfer since only the data format of
+    /// the peripheral is changed. The I2s interface is deactivated and the internal state is reset.
+    /// The prescaler is kept as is, so the sampling rate changes when the channel length changes
+    /// while master clock is disabled.
+    #[allow(non_camel_case_types)]
+    pub fn change_data_format<NEW_FMT>(
+        mut self,
+        _format: NEW_FMT,
+    ) -> I2sTransfer<I, MS, DIR, STD, NEW_FMT>
+    where
+        NEW_FMT: DataFormat,
+        (STD, NEW_FMT): FrameFormat,
+    {
+        self.driver.disable();
+        self.driver.set_data_format(NEW_FMT::VALUE);
+        I2sTransfer::<I, MS, DIR, STD, NEW_FMT>::_from_parts(
+            self.driver,
+            self.options,
+            self.on_resync,
+        )
+    }
+
+    /// Consume the transfer and create a new one using another standard. The parameter is just a
+    /// marker implementing [`I2sStandard`].
+    ///
+    /// This allows switching between PCM and stereo standards without a full reconfiguration,
+    /// since the frame layout is part of the transfer type. The I2s interface is deactivated and
+    /// the internal state is reset. In master mode, the sampling rate is doubled when switching
+    /// to a PCM standard, and halved the other way.
+    #[allow(non_camel_case_types)]
+    pub fn change_standard<NEW_STD>(
+        self,
+        standard: NEW_STD,
+    ) -> I2sTransfer<I, MS, DIR, NEW_STD, FMT>
+    where
+        NEW_STD: I2sStandard,
+        (NEW_STD, FMT): FrameFormat,
+    {
+        I2sTransfer::<I, MS, DIR, NEW_STD, FMT>::_from_parts(
+            self.driver.change_standard(standard),
+            self.options,
+            self.on_resync,
+        )
+    }
+}
+
+/// Conversion from a driver
+impl<I, MS, DIR, STD> Driver<I, MS, DIR, STD>
+where
+    I: I2sPeripheral,
+    STD: I2sStandard,
+{
+    /// Consume the driver and create a transfer around it, using the given data format. The
+    /// parameter is just a marker implementing [`DataFormat`].
+    ///
+    /// The I2s interface is deactivated and the data format is set, other settings are kept as
+    /// is. This avoids releasing and reconfiguring the peripheral when switching to the transfer
+    /// API. Transfer specific settings have their default value.
+    pub fn into_transfer<FMT>(mut self, _format: FMT) -> I2sTransfer<I, MS, DIR, STD, FMT>
+    where
+        FMT: DataFormat,
+        (STD, FMT): FrameFormat,
+    {
+        self.disable();
+        self.set_data_format(FMT::VALUE);
+        I2sTransfer::<I, MS, DIR, STD, FMT>::_from_parts(self, Default::default(), None)
+    }
+}
+
+impl<I, MS, DIR, STD, FMT> I2sTransfer<I, MS, DIR, STD, FMT>
+where
+    I: I2sPeripheral,
+    (STD, FMT): FrameFormat,
+{
+    /// Activate the I2s interface.
+    pub fn begin(&mut self) {
+        self._activate()
+    }
+}
+
+impl<I, DIR, STD, FMT> I2sTransfer<I, Slave, DIR, STD, FMT>
 where
     I: I2sPeripheral,
     (STD, FMT): FrameFormat,
@@ -528,6 +1425,26 @@ where
         self.frame = Default::default();
         self.transfer_count = 0;
         self.sync = false;
+        self.frame_count = 0;
+        self.clip_run = 0;
+        self.clipping = false;
+        self.ws_misaligned = false;
+        self.preloaded = false;
+    }
+
+    /// Return `true` if the last call to a blocking method gave up synchronising, see
+    /// [`I2sTransferConfig::max_sync_attempts`].
+    pub fn sync_failed(&self) -> bool {
+        self.options.sync_attempts_exhausted(self.sync_failures)
+    }
+
+    /// Register a function called each time an error is detected and the transfer
+    /// resynchronises.
+    ///
+    /// This is meant for observability, like counting desynchronisation events. The function is
+    /// called from the read and write methods, so it must not block.
+    pub fn on_resync(&mut self, cb: fn()) {
+        self.on_resync = Some(cb);
     }
 }
 
@@ -543,6 +1460,11 @@ where
         self.frame = Default::default();
         self.transfer_count = 0;
         self.sync = false;
+        self.frame_count = 0;
+        self.clip_run = 0;
+        self.clipping = false;
+        self.ws_misaligned = false;
+        self.preloaded = false;
     }
 }
 
@@ -554,6 +1476,151 @@ where
     pub fn sample_rate(&self) -> u32 {
         self.driver.sample_rate()
     }
+
+    /// Get the current prescaler setting as an `(odd, div)` pair.
+    ///
+    /// When the configuration used `request_frequency`, passing this to
+    /// [`I2sTransferConfig::prescaler`] in a new configuration keeps exactly the current sampling
+    /// rate, as long as master clock, standard and data format are unchanged.
+    pub fn prescaler(&self) -> (bool, u8) {
+        self.driver.prescaler()
+    }
+
+    /// Get the number of bytes per second going through the data register.
+    ///
+    /// This is `sample_rate() * FRAME_WORDS * 2` and is useful to size DMA or ring buffers.
+    pub fn byte_rate(&self) -> u32 {
+        self.sample_rate() * Self::FRAME_WORDS as u32 * 2
+    }
+}
+
+impl<I, MS, STD, FMT> I2sTransfer<I, MS, Transmit, STD, FMT>
+where
+    I: I2sPeripheral,
+    (STD, FMT): FrameFormat,
+{
+    /// Get the last frame written to the transfer, reconstructed from the internal raw frame.
+    ///
+    /// This returns `None` when no frame has been written since the transfer was started. The
+    /// internal frame reflects what is actually transmitted, so the completed part is silent after
+    /// `complete_partial_frame`. After a slave underrun, the frame is kept with the default
+    /// [`UnderflowPolicy::HoldLast`], and the whole frame is silent with [`UnderflowPolicy::Zero`].
+    pub fn last_written<T: FromRawFrame<STD, FMT>>(&self) -> Option<T> {
+        if self.frame_count == 0 {
+            return None;
+        }
+        Some(T::from_raw(self.frame))
+    }
+
+    /// Stage `frame` to be the first frame transmitted, without activating the I2s interface.
+    ///
+    /// The data register holds a single half word, so only the first half word of the frame is
+    /// written to it, and `transfer_count` is set accordingly: the remaining part of the frame is
+    /// written by the next calls to `write` or `write_iter`, like for any frame. This is meant to
+    /// be called while the interface is inactive and at a frame boundary, for example to start
+    /// several peripherals together. `WouldBlock` is returned while the data register still holds
+    /// a previous half word, that is while TXE is clear. The staged frame is counted by
+    /// [`frame_counter`](Self::frame_counter) once the interface is activated.
+    ///
+    /// In slave mode, `write_iter` always synchronises with the first frame of its iterator, so
+    /// the staged frame is only used by `write`.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, this panics if the interface is active or if a frame is partially
+    /// written, since replacing the data register would break the channel alignment.
+    pub fn preload<T: ToRawFrame<STD, FMT>>(&mut self, frame: T) -> nb::Result<(), Infallible> {
+        debug_assert!(
+            !self.driver.is_enabled(),
+            "preload called on an active interface"
+        );
+        debug_assert!(
+            self.transfer_count == 0 || self.transfer_count >= self.frame.as_ref().len() as u8,
+            "preload called in the middle of a frame"
+        );
+        if !self.driver.status().txe() {
+            return Err(WouldBlock);
+        }
+        self._preload(frame);
+        Ok(())
+    }
+
+    fn _preload<T: ToRawFrame<STD, FMT>>(&mut self, frame: T) {
+        self.frame = frame.to_raw();
+        self.driver.write_data_register(self.frame.as_ref()[0]);
+        self.transfer_count = 1;
+        self.preloaded = true;
+    }
+
+    /// Consume the transfer and create a receive transfer on the same peripheral, for half duplex
+    /// protocols sharing a single SD line.
+    ///
+    /// The current frame is completed with zeros and this blocks until the last data is sent,
+    /// then the I2s interface is deactivated and the direction is changed. In master mode, the
+    /// interface is activated again right away, so receiving starts at a frame boundary. In slave
+    /// mode, the next read synchronises with the bus. Clocks stop while the peripheral is
+    /// deactivated, so the turnaround always leaves a gap on the bus.
+    ///
+    /// Completing and sending the frame needs the bit clock: in slave mode, this never returns if
+    /// the master stops clocking the bus before the last data is sent.
+    pub fn turnaround(mut self) -> I2sTransfer<I, MS, Receive, STD, FMT>
+    where
+        MS: Role,
+    {
+        if MS::IS_MASTER || self.sync {
+            self._complete_partial_frame();
+        }
+        loop {
+            let status = self.driver.status();
+            if status.txe() && !status.bsy() {
+                break;
+            }
+        }
+        self._turnaround(Receive)
+    }
+
+    /// Write zeros to the remaining part of the current frame, if any.
+    fn _complete_partial_frame(&mut self) {
+        let len = self.frame.as_ref().len() as u8;
+        if self.transfer_count == 0 {
+            return;
+        }
+        while self.transfer_count < len {
+            if self.driver.status().txe() {
+                self.frame.as_mut()[self.transfer_count as usize] = 0;
+                self.driver.write_data_register(0);
+                self.transfer_count += 1;
+            }
+        }
+    }
+}
+
+/// Transmit methods shared by master and slave transfers, built on the per-role `write_iter`.
+impl<I, MS, STD, FMT> I2sTransfer<I, MS, Transmit, STD, FMT>
+where
+    I: I2sPeripheral,
+    (STD, FMT): FrameFormat,
+    Self: hooks::TransmitHooks<STD, FMT>,
+{
+    /// Transmit (blocking) the frames of `frames` in a loop, until `should_continue` returns
+    /// `false`.
+    ///
+    /// `should_continue` is called before each frame, so transmission stops cleanly at a frame
+    /// boundary. This is intended for looped playback, e.g. a test tone. Nothing is transmitted
+    /// if `frames` is empty.
+    pub fn write_cyclic<T, F>(&mut self, frames: &[T], mut should_continue: F)
+    where
+        T: ToRawFrame<STD, FMT> + Copy,
+        F: FnMut() -> bool,
+    {
+        self._write_frames(
+            frames
+                .iter()
+                .copied()
+                .cycle()
+                .take_while(|_| should_continue()),
+        );
+    }
 }
 
 /// Master Transmit
@@ -562,6 +1629,30 @@ where
     I: I2sPeripheral,
     (STD, FMT): FrameFormat,
 {
+    /// Write zeros to complete a partially transmitted frame, so the bus is left at a frame
+    /// boundary.
+    ///
+    /// This is a recovery helper after an aborted transmission. This does nothing when the
+    /// transfer is already at a frame boundary, otherwise this blocks until the frame is completed.
+    pub fn complete_partial_frame(&mut self) {
+        self._complete_partial_frame();
+    }
+
+    /// Transmit (blocking) raw half words, eg a sync word of a custom protocol layered on the
+    /// bus, and activate the I2s interface if disabled.
+    ///
+    /// Words are written as is to the data register, bypassing the frame semantics: they aren't
+    /// counted as frames, and the position in the current frame is unchanged. It's up to the
+    /// caller to account for channel alignment, typically by writing a multiple of
+    /// [`FRAME_WORDS`](Self::FRAME_WORDS) words at a frame boundary.
+    pub fn write_preamble(&mut self, words: &[u16]) {
+        self._activate();
+        for &word in words {
+            while !self.driver.status().txe() {}
+            self.driver.write_data_register(word);
+        }
+    }
+
     /// Transmit (blocking) data from an iterator.
     pub fn write_iter<ITER, T>(&mut self, samples: ITER)
     where
@@ -569,7 +1660,7 @@ where
         ITER: IntoIterator<Item = T>,
     {
         let mut samples = samples.into_iter();
-        self.driver.enable();
+        self._activate();
         loop {
             let status = self.driver.status();
             if status.txe() {
@@ -585,6 +1676,7 @@ where
                         break;
                     }
                     self.frame = smpl.unwrap().to_raw();
+                    self._count_frame();
                 }
                 self.driver
                     .write_data_register(self.frame.as_ref()[self.transfer_count as usize]);
@@ -593,12 +1685,54 @@ where
         }
     }
 
+    /// Load the first half word of `first` in the data register, then activate the I2s interface.
+    ///
+    /// This ensures the first frame going out is user data instead of the content left in the
+    /// data register. The remaining part of the frame is written by the next calls to `write`,
+    /// like for any frame. This is meant to be called when the interface is inactive, that is
+    /// after instantiation or `end`.
+    pub fn start_transmitting<T: ToRawFrame<STD, FMT>>(&mut self, first: T) {
+        self.driver.disable();
+        self._preload(first);
+        self._activate();
+    }
+
+    /// Activate the I2s interface and block until the TXE flag is set.
+    ///
+    /// When the data register was loaded before, e.g. with `preload`, this returns once that
+    /// data has been moved to the shift register, meaning the peripheral is actually clocking.
+    /// Otherwise, TXE is already set and this returns immediately. This never returns if the data
+    /// register is loaded and the I2S clock isn't running, for example because of a clock
+    /// misconfiguration.
+    pub fn begin_blocking(&mut self) {
+        self._activate();
+        while !self.driver.status().txe() {}
+    }
+
+    /// Transmit (blocking) data from a slice, with a linear fade-in over the first `fade_in`
+    /// frames and a linear fade-out over the last `fade_out` frames.
+    ///
+    /// The first frame of a fade-in and the last frame of a fade-out are silent, avoiding clicks
+    /// at stream boundaries. Gains are computed in fixed-point.
+    pub fn write_slice_fade<T>(&mut self, frames: &[T], fade_in: u32, fade_out: u32)
+    where
+        T: ToRawFrame<STD, FMT> + Gain,
+    {
+        let len = frames.len() as u32;
+        self.write_iter(frames.iter().enumerate().map(|(index, frame)| {
+            frame.apply_gain(fade_gain(index as u32, len, fade_in, fade_out))
+        }));
+    }
+
     /// Write one audio frame and activate the I2s interface if disabled.
     ///
     /// To fully transmit the frame, this function need to be continuously called until the next
     /// frame can be written.
+    ///
+    /// Since there is no FIFO, each call writes at most one half word to the data register, so
+    /// `WouldBlock` is returned until the last half word of the previous frame has been written.
     pub fn write<T: ToRawFrame<STD, FMT>>(&mut self, frame: T) -> nb::Result<(), Infallible> {
-        self.driver.enable();
+        self._activate();
         let status = self.driver.status();
         if status.txe() {
             // having this check before give a chance to optimizer to remove bound checking on
@@ -608,6 +1742,7 @@ where
             }
             if self.transfer_count == 0 {
                 self.frame = frame.to_raw();
+                self._count_frame();
                 self.driver
                     .write_data_register(self.frame.as_ref()[self.transfer_count as usize]);
                 self.transfer_count += 1;
@@ -629,6 +1764,28 @@ where
     STD: I2sStandard,
     (STD, FMT): FrameFormat,
 {
+    /// Write zeros to complete a partially transmitted frame, so the bus is left at a frame
+    /// boundary.
+    ///
+    /// This is a recovery helper after an aborted transmission. This does nothing when the
+    /// transfer is already at a frame boundary or isn't synchronised, otherwise this blocks until
+    /// the frame is completed.
+    pub fn complete_partial_frame(&mut self) {
+        if self.sync {
+            self._complete_partial_frame();
+        }
+    }
+
+    /// Load the data register with the first half word of `frame`, so the first frame
+    /// transmitted after synchronisation is known.
+    ///
+    /// Without this, the data register may already contain data when `write` synchronises with
+    /// the bus. This is [`preload`](Self::preload) under the name used for slave startup: call it
+    /// before the first `write`, which then synchronises and transmits the rest of `frame`.
+    pub fn prime<T: ToRawFrame<STD, FMT>>(&mut self, frame: T) -> nb::Result<(), Infallible> {
+        self.preload(frame)
+    }
+
     /// Transmit (blocking) data from an iterator.
     pub fn write_iter<ITER, T>(&mut self, frames: ITER)
     where
@@ -636,6 +1793,7 @@ where
         ITER: IntoIterator<Item = T>,
     {
         let mut frames = frames.into_iter();
+        self.sync_failures = 0;
         loop {
             if self.sync {
                 let status = self.driver.status();
@@ -652,6 +1810,7 @@ where
                             break;
                         }
                         self.frame = frm.unwrap().to_raw();
+                        self._count_frame();
                     }
                     self.driver
                         .write_data_register(self.frame.as_ref()[self.transfer_count as usize]);
@@ -659,9 +1818,10 @@ where
                 }
                 if status.fre() || status.udr() {
                     self.sync = false;
+                    self._notify_resync();
                     self.driver.disable();
                 }
-            } else if !self._ws_is_start() {
+            } else if self._ws_is_sync_ready() {
                 // data register may (or not) already contain data, causing uncertainty about next
                 // time txe flag is set. Writing it remove the uncertainty.
                 let frm = frames.next();
@@ -669,15 +1829,22 @@ where
                 if frm.is_none() {
                     break;
                 }
+                // a frame staged by preload is replaced, so it's never counted
+                self.preloaded = false;
                 self.frame = frm.unwrap().to_raw();
+                self._count_frame();
                 self.driver.write_data_register(self.frame.as_ref()[0]);
                 self.transfer_count = 1;
-                self.driver.enable();
+                self._activate();
                 // ensure the ws line didn't change during sync process
                 if !self._ws_is_start() {
                     self.sync = true;
+                    self.sync_failures = 0;
                 } else {
                     self.driver.disable();
+                    if self._sync_attempt_failed() {
+                        break;
+                    }
                 }
             }
         }
@@ -687,7 +1854,25 @@ where
     ///
     /// To fully transmit the frame, this function need to be continuously called until the next
     /// frame can be written.
+    ///
+    /// Since there is no FIFO, each call writes at most one half word to the data register, so
+    /// `WouldBlock` is returned until the last half word of the previous frame has been written.
     pub fn write<T: ToRawFrame<STD, FMT>>(&mut self, frame: T) -> nb::Result<(), Infallible> {
+        match self.write_checked(frame) {
+            Err(nb::Error::Other(_)) => Err(WouldBlock),
+            Err(WouldBlock) => Err(WouldBlock),
+            Ok(()) => Ok(()),
+        }
+    }
+
+    /// Like `write`, but return the error when a frame error or an underrun is detected.
+    ///
+    /// The transfer still recovers by resynchronising, like with `write`, so the caller can just
+    /// keep on writing.
+    pub fn write_checked<T: ToRawFrame<STD, FMT>>(
+        &mut self,
+        frame: T,
+    ) -> nb::Result<(), I2sTransferError> {
         if self.sync {
             let status = self.driver.status();
             if status.txe() {
@@ -698,6 +1883,7 @@ where
                 }
                 if self.transfer_count == 0 {
                     self.frame = frame.to_raw();
+                    self._count_frame();
                     self.driver
                         .write_data_register(self.frame.as_ref()[self.transfer_count as usize]);
                     self.transfer_count += 1;
@@ -710,17 +1896,26 @@ where
             }
             if status.fre() || status.udr() {
                 self.sync = false;
+                self._notify_resync();
+                if self.options.underflow_policy == UnderflowPolicy::Zero {
+                    self.frame = Default::default();
+                }
                 self.driver.disable();
+                return Err(nb::Error::Other(match status.fre() {
+                    true => I2sTransferError::FrameError,
+                    false => I2sTransferError::Underrun,
+                }));
             }
-        } else if !self._ws_is_start() {
+        } else if self._ws_is_sync_ready() {
             // data register may (or not) already contain data, causing uncertainty about next
             // time txe flag is set. Writing it remove the uncertainty.
             self.driver.write_data_register(self.frame.as_ref()[0]);
             self.transfer_count = 1;
-            self.driver.enable();
+            self._activate();
             // ensure the ws line didn't change during sync process
             if !self._ws_is_start() {
                 self.sync = true;
+                self.sync_failures = 0;
             } else {
                 self.driver.disable();
             }
@@ -730,12 +1925,474 @@ where
     }
 }
 
+mod hooks {
+    use super::*;
+
+    /// Role specific parts of the transmit methods shared by master and slave transfers.
+    pub trait TransmitHooks<STD, FMT>
+    where
+        (STD, FMT): FrameFormat,
+    {
+        /// Blocking write loop, that is `write_iter`.
+        fn _write_frames<ITER, T>(&mut self, frames: ITER)
+        where
+            T: ToRawFrame<STD, FMT>,
+            ITER: IntoIterator<Item = T>;
+    }
+
+    /// Role specific parts of the receive methods shared by master and slave transfers.
+    pub trait ReceiveHooks<STD, FMT>
+    where
+        (STD, FMT): FrameFormat,
+    {
+        /// Blocking read loop of `read_while`, also giving the CHSIDE flag of the last half word
+        /// of each frame to `predicate`. Slaves never fail.
+        fn _read_frames<T, F>(&mut self, predicate: F) -> Result<(), I2sTransferError>
+        where
+            T: FromRawFrame<STD, FMT>,
+            F: FnMut(T, bool) -> bool;
+
+        /// Activate the interface so the next received half word starts a frame.
+        fn _start_at_frame(&mut self);
+
+        /// Deactivate the interface and reset the internal state, that is `end`.
+        fn _end(&mut self);
+    }
+
+    impl<I, STD, FMT> TransmitHooks<STD, FMT> for I2sTransfer<I, Master, Transmit, STD, FMT>
+    where
+        I: I2sPeripheral,
+        (STD, FMT): FrameFormat,
+    {
+        fn _write_frames<ITER, T>(&mut self, frames: ITER)
+        where
+            T: ToRawFrame<STD, FMT>,
+            ITER: IntoIterator<Item = T>,
+        {
+            self.write_iter(frames)
+        }
+    }
+
+    impl<I, STD, FMT> TransmitHooks<STD, FMT> for I2sTransfer<I, Slave, Transmit, STD, FMT>
+    where
+        I: I2sPeripheral,
+        STD: I2sStandard,
+        (STD, FMT): FrameFormat,
+    {
+        fn _write_frames<ITER, T>(&mut self, frames: ITER)
+        where
+            T: ToRawFrame<STD, FMT>,
+            ITER: IntoIterator<Item = T>,
+        {
+            self.write_iter(frames)
+        }
+    }
+
+    impl<I, STD, FMT> ReceiveHooks<STD, FMT> for I2sTransfer<I, Master, Receive, STD, FMT>
+    where
+        I: I2sPeripheral,
+        STD: I2sStandard,
+        (STD, FMT): FrameFormat,
+    {
+        fn _read_frames<T, F>(&mut self, predicate: F) -> Result<(), I2sTransferError>
+        where
+            T: FromRawFrame<STD, FMT>,
+            F: FnMut(T, bool) -> bool,
+        {
+            self._read_while(predicate)
+        }
+
+        fn _start_at_frame(&mut self) {
+            self.end();
+            self.driver.enable();
+        }
+
+        fn _end(&mut self) {
+            self.end()
+        }
+    }
+
+    impl<I, STD, FMT> ReceiveHooks<STD, FMT> for I2sTransfer<I, Slave, Receive, STD, FMT>
+    where
+        I: I2sPeripheral,
+        STD: I2sStandard,
+        (STD, FMT): FrameFormat,
+    {
+        fn _read_frames<T, F>(&mut self, predicate: F) -> Result<(), I2sTransferError>
+        where
+            T: FromRawFrame<STD, FMT>,
+            F: FnMut(T, bool) -> bool,
+        {
+            self._read_while(predicate);
+            Ok(())
+        }
+
+        fn _start_at_frame(&mut self) {
+            while self.start_when_clocked().is_err() {}
+        }
+
+        fn _end(&mut self) {
+            self.end()
+        }
+    }
+}
+
+/// Channel corresponding to a CHSIDE flag value.
+fn chside_channel(chside: bool) -> Channel {
+    match chside {
+        false => Channel::Left,
+        true => Channel::Right,
+    }
+}
+
+/// Frames accounted by the gap estimation for an overrun recovery in master mode.
+const MASTER_GAP_FRAMES: u32 = 1;
+/// Frames accounted by the gap estimation for a resynchronisation in slave mode.
+const SLAVE_GAP_FRAMES: u32 = 2;
+
+/// Number of buckets per channel of the amplitude histogram, see [`I2sTransfer::histogram`].
+pub const HISTOGRAM_BUCKETS: usize = 8;
+
+/// Default number of consecutive frames with a full scale sample for the clipping detection to
+/// trigger, see [`I2sTransferConfig::clip_frames`].
+pub const CLIP_FRAMES: u8 = 4;
+
+impl<I, MS, STD, FMT> I2sTransfer<I, MS, Receive, STD, FMT>
+where
+    I: I2sPeripheral,
+    (STD, FMT): FrameFormat,
+{
+    /// Consume the transfer and create a transmit transfer on the same peripheral, for half
+    /// duplex protocols sharing a single SD line.
+    ///
+    /// The I2s interface is deactivated, the direction is changed and the partially received
+    /// frame is dropped. The next write activates the interface again, synchronising with the
+    /// bus in slave mode. Clocks stop while the peripheral is deactivated, so the turnaround
+    /// always leaves a gap on the bus.
+    pub fn turnaround(self) -> I2sTransfer<I, MS, Transmit, STD, FMT>
+    where
+        MS: Role,
+    {
+        self._turnaround(Transmit)
+    }
+
+    /// Return `true` if clipping has been detected since the transfer started or since the last
+    /// call to [`clear_clipping`](Self::clear_clipping), that is a sample was at full scale for
+    /// the number of consecutive frames set by [`I2sTransferConfig::clip_frames`].
+    ///
+    /// This is always `false` unless clipping detection is enabled with
+    /// [`I2sTransferConfig::clip_detect`].
+    pub fn was_clipping(&self) -> bool {
+        self.clipping
+    }
+
+    /// Clear the clipping flag, for example after changing the gain of the source.
+    pub fn clear_clipping(&mut self) {
+        self.clip_run = 0;
+        self.clipping = false;
+    }
+
+    /// Get an estimation of the number of frames lost by error recoveries since the transfer was
+    /// created. This is not reset by `end`.
+    ///
+    /// In master mode, an overrun loses the frame in progress, and the clocks restart at a frame
+    /// boundary, so each recovery is accounted as 1 frame. In slave mode, a resynchronisation loses
+    /// the frame in progress, and the peripheral can only be activated during the second half of
+    /// a frame, so each resynchronisation is accounted as 2 frames. Frames elapsed while
+    /// reception is stopped, e.g. between `end` and the next read, aren't known and so aren't
+    /// included.
+    ///
+    /// This is always 0 unless the estimation is enabled with
+    /// [`I2sTransferConfig::detect_gaps`].
+    pub fn gap_frames_estimate(&self) -> u32 {
+        self.gap_frames
+    }
+
+    #[inline]
+    fn _record_gap(&mut self, lost_frames: u32) {
+        if self.options.detect_gaps {
+            self.gap_frames = self.gap_frames.saturating_add(lost_frames);
+        }
+    }
+
+    /// Get the amplitude histogram of received samples, per channel, since the transfer was
+    /// created or since the last call to [`clear_histogram`](Self::clear_histogram). This is not
+    /// reset by `end`.
+    ///
+    /// Buckets are about 12 dB wide: with `m` the magnitude of the 16 most significant bits of a
+    /// sample, bucket 0 counts samples with `m < 4`, bucket `k` counts samples with
+    /// `4^k <= m < 4^(k + 1)`, and bucket 7 counts samples with `m >= 4^7`. The first array is
+    /// the left channel, and the only one used with PCM standards.
+    ///
+    /// This is always zero unless the histogram is enabled with
+    /// [`I2sTransferConfig::collect_histogram`].
+    pub fn histogram(&self) -> [[u32; HISTOGRAM_BUCKETS]; 2] {
+        self.histogram
+    }
+
+    /// Clear the amplitude histogram.
+    pub fn clear_histogram(&mut self) {
+        self.histogram = Default::default();
+    }
+
+    /// Get the channel of the last half word read from the data register, according to the
+    /// CHSIDE flag read along with it.
+    ///
+    /// This gives per word channel context, which is otherwise hidden by audio frames. Since the
+    /// non-blocking `read` reads at most one half word per call, this tells which channel was
+    /// just received when it returns `WouldBlock`. Blocking methods borrow the transfer, so this
+    /// can't be called from their closures: `read_while_with_channel` gives the same information
+    /// to its predicate. Before the first read, this is `Left`.
+    pub fn current_frame_channel(&self) -> Channel
+    where
+        STD: ChannelFlag,
+    {
+        chside_channel(self.last_chside)
+    }
+
+    /// Read `frames` frames, discarding data, and check that the CHSIDE flag toggles after each
+    /// sample. Return `false` on overrun.
+    fn _chside_alternates(&mut self, frames: u32) -> bool
+    where
+        STD: ChannelFlag,
+    {
+        let sample_words = <(STD, FMT) as FrameFormat>::SAMPLE_WORDS;
+        let mut first = None;
+        for i in 0..frames as usize * Self::FRAME_WORDS {
+            let status = loop {
+                let status = self.driver.status();
+                if status.ovr() {
+                    return false;
+                }
+                if status.rxne() {
+                    break status;
+                }
+            };
+            self.driver.read_data_register();
+            self.last_chside = status.chside_bit();
+            let right = self.last_chside;
+            let first = *first.get_or_insert(right);
+            if right != (first ^ ((i / sample_words) % 2 == 1)) {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[inline]
+    fn _count_received_frame(&mut self) {
+        self._count_frame();
+        if self.options.histogram {
+            let words = <(STD, FMT) as FrameFormat>::SAMPLE_WORDS;
+            for (channel, sample) in self.frame.as_ref().chunks(words).enumerate() {
+                let magnitude = (sample[0] as i16).unsigned_abs();
+                let bits = (u16::BITS - magnitude.leading_zeros()) as usize;
+                let bucket = (bits.saturating_sub(1) / 2).min(HISTOGRAM_BUCKETS - 1);
+                let count = &mut self.histogram[channel][bucket];
+                *count = count.saturating_add(1);
+            }
+        }
+        if !self.options.clip_detect {
+            return;
+        }
+        let words = self.frame.as_ref();
+        let full_scale = match <(STD, FMT) as FrameFormat>::SAMPLE_WORDS {
+            1 => words.iter().any(|&w| w == 0x7fff || w == 0x8000),
+            _ => words
+                .chunks(2)
+                .any(|s| s == [0x7fff, 0xffff] || s == [0x8000, 0x0000]),
+        };
+        if full_scale {
+            self.clip_run = self.clip_run.saturating_add(1);
+            self.clipping |= self.clip_run >= self.options.clip_frames.max(1);
+        } else {
+            self.clip_run = 0;
+        }
+    }
+}
+
+/// Receive methods shared by master and slave transfers, built on the per-role `read_while`.
+impl<I, MS, STD, FMT> I2sTransfer<I, MS, Receive, STD, FMT>
+where
+    I: I2sPeripheral,
+    (STD, FMT): FrameFormat,
+    Self: hooks::ReceiveHooks<STD, FMT>,
+{
+    /// Read samples while predicate returns `true`, decimating them by `factor`.
+    ///
+    /// The predicate is called with the average of each group of `factor` consecutive frames, so
+    /// audio data can be stored at a lower rate than the capture rate. Averaging is done with
+    /// widened accumulators. A `factor` of 0 is handled like 1. The given closure must not block,
+    /// otherwise communication problems may occur. Errors are handled like with `read_while`, so
+    /// this only fails on a master overrun.
+    pub fn read_decimated_while<F, T>(
+        &mut self,
+        factor: u32,
+        mut predicate: F,
+    ) -> Result<(), I2sTransferError>
+    where
+        T: FromRawFrame<STD, FMT> + Average,
+        F: FnMut(T) -> bool,
+    {
+        let factor = factor.max(1);
+        let mut sum = T::Sum::default();
+        let mut count = 0;
+        self._read_frames(|frame: T, _| {
+            sum = frame.accumulate(sum);
+            count += 1;
+            if count < factor {
+                return true;
+            }
+            let average = T::average(sum, count);
+            sum = Default::default();
+            count = 0;
+            predicate(average)
+        })
+    }
+
+    /// Read samples while predicate returns `Ok(true)`.
+    ///
+    /// This is like `read_while`, but the predicate can fail. When it returns an error, reading is
+    /// stopped and the error is returned. In master mode, an overrun is reported as
+    /// `ReadWhileError::Overrun`. In slave mode, errors on the bus are recovered like with
+    /// `read_while`, but giving up synchronising is reported as `ReadWhileError::SyncFailed`. The
+    /// given closure must not block, otherwise communication problems may occur.
+    pub fn try_read_while<F, T, E>(&mut self, mut predicate: F) -> Result<(), ReadWhileError<E>>
+    where
+        T: FromRawFrame<STD, FMT>,
+        F: FnMut(T) -> Result<bool, E>,
+    {
+        let mut error = None;
+        let res = self._read_frames(|frame, _| match predicate(frame) {
+            Ok(cont) => cont,
+            Err(e) => {
+                error = Some(e);
+                false
+            }
+        });
+        match (error, res) {
+            (Some(e), _) => Err(ReadWhileError::Predicate(e)),
+            // read_while can only fail with a master overrun
+            (None, Err(_)) => Err(ReadWhileError::Overrun),
+            // sync failures are only accounted in slave mode
+            (None, Ok(())) if self.options.sync_attempts_exhausted(self.sync_failures) => {
+                Err(ReadWhileError::SyncFailed)
+            }
+            (None, Ok(())) => Ok(()),
+        }
+    }
+
+    /// Fill `chunk` with audio frames, call `on_full` with the whole chunk, and repeat while
+    /// `on_full` returns `true`.
+    ///
+    /// This is intended for block based processing. `on_full` is called between two frames, so
+    /// it must return before the next data arrives, otherwise an overrun occurs and data are
+    /// lost. Nothing is read if `chunk` is empty. Errors are handled like with `read_while`, so
+    /// this only fails on a master overrun.
+    pub fn read_chunks<F, T>(
+        &mut self,
+        chunk: &mut [T],
+        mut on_full: F,
+    ) -> Result<(), I2sTransferError>
+    where
+        T: FromRawFrame<STD, FMT>,
+        F: FnMut(&[T]) -> bool,
+    {
+        if chunk.is_empty() {
+            return Ok(());
+        }
+        let mut idx = 0;
+        self._read_frames(|frame, _| {
+            chunk[idx] = frame;
+            idx += 1;
+            if idx < chunk.len() {
+                return true;
+            }
+            idx = 0;
+            on_full(chunk)
+        })
+    }
+
+    /// Read `count` audio frames and append them to `sink`, e.g. a `heapless::Vec`.
+    ///
+    /// `sink` is extended once per frame, so its `extend` implementation must return before the
+    /// next data arrives. Nothing is read if `count` is zero. Errors are handled like with
+    /// `read_while`, so this only fails on a master overrun. In slave mode, this stops early when
+    /// synchronisation attempts are exhausted, see [`I2sTransferConfig::max_sync_attempts`].
+    pub fn read_extend<T, E>(&mut self, sink: &mut E, count: usize) -> Result<(), I2sTransferError>
+    where
+        T: FromRawFrame<STD, FMT>,
+        E: Extend<T>,
+    {
+        if count == 0 {
+            return Ok(());
+        }
+        let mut remaining = count;
+        self._read_frames(|frame, _| {
+            sink.extend(Some(frame));
+            remaining -= 1;
+            remaining > 0
+        })
+    }
+
+    /// Check that received data alternate between left and right channels, to detect a codec
+    /// sending mono data or another framing problem.
+    ///
+    /// This reads `frames` frames and checks that the CHSIDE flag toggles after each sample.
+    /// Received data are discarded, and the transfer is ended afterwards. In master mode, the
+    /// transfer is also ended before the check, so it starts at a frame boundary. In slave mode,
+    /// this first synchronises like `start_when_clocked`, so it never returns if there is no
+    /// clock. Return `false` if the flag doesn't toggle as expected, or if an overrun occurs.
+    pub fn verify_stereo_framing(&mut self, frames: u32) -> bool
+    where
+        STD: ChannelFlag,
+    {
+        self._start_at_frame();
+        let aligned = self._chside_alternates(frames);
+        self._end();
+        aligned
+    }
+}
+
 /// Master Receive
 impl<I, STD, FMT> I2sTransfer<I, Master, Receive, STD, FMT>
 where
     I: I2sPeripheral,
+    STD: I2sStandard,
     (STD, FMT): FrameFormat,
 {
+    /// Return `true` if the WS line was found at an unexpected level at the end of a frame, which
+    /// means that the received frames are shifted. This is reset by `end`.
+    ///
+    /// This is always `false` unless the verification is enabled with
+    /// [`I2sTransferConfig::verify_ws_alignment`].
+    pub fn ws_misaligned(&self) -> bool {
+        self.ws_misaligned
+    }
+
+    /// The last half word of a frame has just been read while the first half word of the next
+    /// frame is being received, so the WS line must be at the start level.
+    #[inline]
+    fn _check_ws_alignment(&mut self) {
+        use crate::driver::I2sStandard::{PcmLongSync, PcmShortSync};
+        if !self.options.verify_ws_alignment || matches!(STD::VALUE, PcmShortSync | PcmLongSync) {
+            return;
+        }
+        if !self._ws_is_start() {
+            self.ws_misaligned = true;
+        }
+    }
+
+    /// Activate the I2s interface and block until the RXNE flag is set, meaning data is actually
+    /// received.
+    ///
+    /// The received data is kept for the next read. This never returns if the I2S clock isn't
+    /// running, for example because of a clock misconfiguration.
+    pub fn begin_blocking(&mut self) {
+        self.driver.enable();
+        while !self.driver.status().rxne() {}
+    }
+
     /// Read samples while predicate return `true`.
     ///
     /// The given closure must not block, otherwise communication problems may occur.
@@ -743,6 +2400,32 @@ where
     where
         T: FromRawFrame<STD, FMT>,
         F: FnMut(T) -> bool,
+    {
+        self._read_while(|frame, _| predicate(frame))
+    }
+
+    /// Like `read_while`, but also give the predicate the channel of the last half word of the
+    /// frame, according to the CHSIDE flag read along with it.
+    ///
+    /// This is what [`current_frame_channel`](Self::current_frame_channel) would return, which
+    /// can't be called from the closure. With a frame aligned with the bus, this is `Right`.
+    pub fn read_while_with_channel<F, T>(
+        &mut self,
+        mut predicate: F,
+    ) -> Result<(), I2sTransferError>
+    where
+        STD: ChannelFlag,
+        T: FromRawFrame<STD, FMT>,
+        F: FnMut(T, Channel) -> bool,
+    {
+        self._read_while(|frame, chside| predicate(frame, chside_channel(chside)))
+    }
+
+    /// Body of `read_while`, giving the CHSIDE flag of the last half word to the predicate.
+    fn _read_while<F, T>(&mut self, mut predicate: F) -> Result<(), I2sTransferError>
+    where
+        T: FromRawFrame<STD, FMT>,
+        F: FnMut(T, bool) -> bool,
     {
         self.driver.enable();
         loop {
@@ -751,48 +2434,132 @@ where
                 if self.transfer_count >= self.frame.as_ref().len() as u8 {
                     self.transfer_count = 0;
                 }
+                self.last_chside = status.chside_bit();
                 self.frame.as_mut()[self.transfer_count as usize] =
                     self.driver.read_data_register();
                 self.transfer_count += 1;
 
-                // note: boolean operators are short-circuiting
-                if self.transfer_count >= self.frame.as_ref().len() as u8
-                    && !predicate(T::from_raw(self.frame))
-                {
-                    return Ok(());
+                if self.transfer_count >= self.frame.as_ref().len() as u8 {
+                    self._count_received_frame();
+                    self._check_ws_alignment();
+                    if !predicate(T::from_raw(self.frame), self.last_chside) {
+                        return Ok(());
+                    }
                 }
             }
             if status.ovr() {
+                self._record_gap(MASTER_GAP_FRAMES);
                 self.end();
                 return Err(I2sTransferError::Overrun);
             }
         }
     }
 
+    /// Read samples while predicate returns `true`, recovering from overruns like in slave mode.
+    ///
+    /// When an overrun occurs, clocks are reset and reading restarts at the beginning of a new
+    /// frame, so this never fails. Data received during the overrun and the partially received
+    /// frame are lost without notice; the predicate is only called with complete frames, but
+    /// consecutive calls may not correspond to consecutive frames on the bus.
+    ///
+    /// The given closure must not block, otherwise communication problems may occur.
+    pub fn read_while_resilient<F, T>(&mut self, mut predicate: F)
+    where
+        T: FromRawFrame<STD, FMT>,
+        F: FnMut(T) -> bool,
+    {
+        self.driver.enable();
+        loop {
+            let status = self.driver.status();
+            if status.rxne() {
+                if self.transfer_count >= self.frame.as_ref().len() as u8 {
+                    self.transfer_count = 0;
+                }
+                self.last_chside = status.chside_bit();
+                self.frame.as_mut()[self.transfer_count as usize] =
+                    self.driver.read_data_register();
+                self.transfer_count += 1;
+
+                if self.transfer_count >= self.frame.as_ref().len() as u8 {
+                    self._count_received_frame();
+                    self._check_ws_alignment();
+                    if !predicate(T::from_raw(self.frame)) {
+                        return;
+                    }
+                }
+            }
+            if status.ovr() {
+                self._record_gap(MASTER_GAP_FRAMES);
+                self.driver.disable();
+                self.driver.reset_clocks();
+                self.transfer_count = 0;
+                self.driver.enable();
+            }
+        }
+    }
+
     /// Read one audio frame and activate the I2s interface if disabled.
     ///
     /// To get the audio frame, this function needs to be continuously called until the frame is
     /// returned
     pub fn read<T: FromRawFrame<STD, FMT>>(&mut self) -> nb::Result<T, I2sTransferError> {
+        self.read_with(|| ()).map(|(frame, _)| frame)
+    }
+
+    /// Like `read`, but also return the raw half words the frame was decoded from, in the order
+    /// they were read from the data register.
+    ///
+    /// This is a debugging aid, for example to check frame decoding against the data on the bus.
+    pub fn read_verbose<T: FromRawFrame<STD, FMT>>(
+        &mut self,
+    ) -> nb::Result<(T, RawFrame<STD, FMT>), I2sTransferError> {
+        self.read_with(|| ()).map(|(frame, _)| (frame, self.frame))
+    }
+
+    /// Like `read`, but call `stamp` right after the last half word of a frame is read, and
+    /// return its result with the frame.
+    ///
+    /// This allows to correlate frames with a timer, for example to get frame accurate
+    /// timestamps. `stamp` must be fast, otherwise an overrun may occur.
+    pub fn read_with<T, S, F>(&mut self, mut stamp: F) -> nb::Result<(T, S), I2sTransferError>
+    where
+        T: FromRawFrame<STD, FMT>,
+        F: FnMut() -> S,
+    {
         self.driver.enable();
         let status = self.driver.status();
         if status.rxne() {
             if self.transfer_count >= self.frame.as_ref().len() as u8 {
                 self.transfer_count = 0;
             }
+            self.last_chside = status.chside_bit();
             self.frame.as_mut()[self.transfer_count as usize] = self.driver.read_data_register();
             self.transfer_count += 1;
 
             if self.transfer_count >= self.frame.as_ref().len() as u8 {
-                return Ok(T::from_raw(self.frame));
+                let stamp = stamp();
+                self._count_received_frame();
+                self._check_ws_alignment();
+                return Ok((T::from_raw(self.frame), stamp));
             }
         }
         if status.ovr() {
+            self._record_gap(MASTER_GAP_FRAMES);
             self.end();
             return Err(nb::Error::Other(I2sTransferError::Overrun));
         }
         Err(WouldBlock)
     }
+
+    /// Return `true` if an overrun occurred, without any recovery attempt.
+    ///
+    /// Unlike `read` and `read_while`, this doesn't end the transfer, so the caller can choose how
+    /// to handle the error. Since the OVR flag is cleared by a read of the data register followed
+    /// by a read of the status register, calling this after a data register read may clear the
+    /// flag.
+    pub fn overrun_pending(&mut self) -> bool {
+        self.driver.status().ovr()
+    }
 }
 
 impl<I, STD, FMT> I2sTransfer<I, Slave, Receive, STD, FMT>
@@ -809,6 +2576,30 @@ where
         T: FromRawFrame<STD, FMT>,
         F: FnMut(T) -> bool,
     {
+        self._read_while(|frame, _| predicate(frame))
+    }
+
+    /// Like `read_while`, but also give the predicate the channel of the last half word of the
+    /// frame, according to the CHSIDE flag read along with it.
+    ///
+    /// This is what [`current_frame_channel`](Self::current_frame_channel) would return, which
+    /// can't be called from the closure. With a frame aligned with the bus, this is `Right`.
+    pub fn read_while_with_channel<F, T>(&mut self, mut predicate: F)
+    where
+        STD: ChannelFlag,
+        T: FromRawFrame<STD, FMT>,
+        F: FnMut(T, Channel) -> bool,
+    {
+        self._read_while(|frame, chside| predicate(frame, chside_channel(chside)))
+    }
+
+    /// Body of `read_while`, giving the CHSIDE flag of the last half word to the predicate.
+    fn _read_while<F, T>(&mut self, mut predicate: F)
+    where
+        T: FromRawFrame<STD, FMT>,
+        F: FnMut(T, bool) -> bool,
+    {
+        self.sync_failures = 0;
         loop {
             if self.sync {
                 let status = self.driver.status();
@@ -816,41 +2607,153 @@ where
                     if self.transfer_count >= self.frame.as_ref().len() as u8 {
                         self.transfer_count = 0;
                     }
+                    self.last_chside = status.chside_bit();
                     self.frame.as_mut()[self.transfer_count as usize] =
                         self.driver.read_data_register();
                     self.transfer_count += 1;
 
-                    // note: boolean operators are short-circuiting
-                    if self.transfer_count >= self.frame.as_ref().len() as u8
-                        && !predicate(T::from_raw(self.frame))
-                    {
-                        return;
+                    if self.transfer_count >= self.frame.as_ref().len() as u8 {
+                        self._count_received_frame();
+                        if !predicate(T::from_raw(self.frame), self.last_chside) {
+                            return;
+                        }
                     }
                 }
                 if status.fre() || status.ovr() {
                     self.sync = false;
+                    self._notify_resync();
+                    self._record_gap(SLAVE_GAP_FRAMES);
                     self.driver.read_data_register();
                     self.driver.status();
                     self.driver.disable();
                 }
-            } else if !self._ws_is_start() {
+            } else if self._ws_is_sync_ready() {
                 self.transfer_count = 0;
                 self.driver.enable();
                 // ensure the ws line didn't change during sync process
                 if !self._ws_is_start() {
                     self.sync = true;
+                    self.sync_failures = 0;
                 } else {
                     self.driver.disable();
+                    if self._sync_attempt_failed() {
+                        return;
+                    }
                 }
             }
         }
     }
 
+    /// Synchronise with the bus and activate the I2s interface.
+    ///
+    /// This function needs to be continuously called until it returns `Ok`, which happens once
+    /// the transfer is synchronised and the first data has been received, meaning the bit clock is
+    /// running. The received data is kept for the next `read`. Since this never returns if there
+    /// is no clock, a timeout may be needed when polling it.
+    pub fn start_when_clocked(&mut self) -> nb::Result<(), Infallible> {
+        if self.sync {
+            let status = self.driver.status();
+            if status.fre() || status.ovr() {
+                self.sync = false;
+                self._notify_resync();
+                self._record_gap(SLAVE_GAP_FRAMES);
+                self.driver.disable();
+            } else if status.rxne() {
+                return Ok(());
+            }
+        } else if self._ws_is_sync_ready() {
+            self.transfer_count = 0;
+            self.driver.enable();
+            self.driver.read_data_register();
+            self.driver.status();
+            // ensure the ws line didn't change during sync process
+            if !self._ws_is_start() {
+                self.sync = true;
+                self.sync_failures = 0;
+            } else {
+                self.driver.disable();
+            }
+        }
+        Err(WouldBlock)
+    }
+
     /// Read one audio frame and activate the I2s interface if disabled.
     ///
     /// To get the audio frame, this function need to be continuously called until the frame is
     /// returned
     pub fn read<T: FromRawFrame<STD, FMT>>(&mut self) -> nb::Result<T, Infallible> {
+        match self.read_status() {
+            ReadOutcome::Frame(frame) => Ok(frame),
+            _ => Err(WouldBlock),
+        }
+    }
+
+    /// Like `read`, but also return the raw half words the frame was decoded from, in the order
+    /// they were read from the data register.
+    ///
+    /// This is a debugging aid, for example to check frame decoding against the data on the bus.
+    pub fn read_verbose<T: FromRawFrame<STD, FMT>>(
+        &mut self,
+    ) -> nb::Result<(T, RawFrame<STD, FMT>), Infallible> {
+        self.read().map(|frame| (frame, self.frame))
+    }
+
+    /// Like `read`, but also report when an error has been detected.
+    ///
+    /// `read` silently recovers from frame errors and overruns by resynchronising. This returns
+    /// [`ReadOutcome::Recovered`] when that happens, which is useful for link diagnostics.
+    pub fn read_status<T: FromRawFrame<STD, FMT>>(&mut self) -> ReadOutcome<T> {
+        match self._read_with(|| ()) {
+            Ok((frame, _)) => ReadOutcome::Frame(frame),
+            Err(WouldBlock) => ReadOutcome::Pending,
+            Err(nb::Error::Other(_)) => ReadOutcome::Recovered,
+        }
+    }
+
+    /// Like `read`, but return the error when a frame error or an overrun is detected.
+    ///
+    /// The transfer still recovers by resynchronising, like with `read`, so the caller can just
+    /// keep on reading.
+    pub fn read_checked<T: FromRawFrame<STD, FMT>>(&mut self) -> nb::Result<T, I2sTransferError> {
+        self._read_with(|| ()).map(|(frame, _)| frame)
+    }
+
+    /// Like `read`, but call `stamp` right after the last half word of a frame is read, and
+    /// return its result with the frame.
+    ///
+    /// This allows to correlate frames with a timer, for example to get frame accurate
+    /// timestamps. `stamp` must be fast, otherwise data may be lost.
+    pub fn read_with<T, S, F>(&mut self, stamp: F) -> nb::Result<(T, S), Infallible>
+    where
+        T: FromRawFrame<STD, FMT>,
+        F: FnMut() -> S,
+    {
+        match self._read_with(stamp) {
+            Ok(frame) => Ok(frame),
+            Err(_) => Err(WouldBlock),
+        }
+    }
+
+    /// Get the most recently received frame again, without reading the peripheral.
+    ///
+    /// This returns `None` when no frame is available, that is when the transfer isn't
+    /// synchronised or when the first half word of the next frame has already been read.
+    /// Otherwise, this is the frame last returned by a read method.
+    pub fn peek<T: FromRawFrame<STD, FMT>>(&self) -> Option<T> {
+        if !self.sync || self.frame_count == 0 {
+            return None;
+        }
+        if self.transfer_count < self.frame.as_ref().len() as u8 {
+            return None;
+        }
+        Some(T::from_raw(self.frame))
+    }
+
+    fn _read_with<T, S, F>(&mut self, mut stamp: F) -> nb::Result<(T, S), I2sTransferError>
+    where
+        T: FromRawFrame<STD, FMT>,
+        F: FnMut() -> S,
+    {
         if !self.sync {
             self.driver.disable();
             self.transfer_count = 0;
@@ -861,22 +2764,31 @@ where
                 if self.transfer_count >= self.frame.as_ref().len() as u8 {
                     self.transfer_count = 0;
                 }
+                self.last_chside = status.chside_bit();
                 self.frame.as_mut()[self.transfer_count as usize] =
                     self.driver.read_data_register();
                 self.transfer_count += 1;
 
                 // note: boolean operators are short-circuiting
                 if self.transfer_count >= self.frame.as_ref().len() as u8 {
-                    return Ok(T::from_raw(self.frame));
+                    let stamp = stamp();
+                    self._count_received_frame();
+                    return Ok((T::from_raw(self.frame), stamp));
                 }
             }
             if status.fre() || status.ovr() {
                 self.sync = false;
+                self._notify_resync();
+                self._record_gap(SLAVE_GAP_FRAMES);
                 //self.driver.read_data_register();
                 //self.driver.status();
                 self.driver.disable();
+                return Err(nb::Error::Other(match status.fre() {
+                    true => I2sTransferError::FrameError,
+                    false => I2sTransferError::Overrun,
+                }));
             }
-        } else if !self._ws_is_start() {
+        } else if self._ws_is_sync_ready() {
             self.transfer_count = 0;
             self.driver.enable();
             self.driver.read_data_register();
@@ -884,6 +2796,7 @@ where
             // ensure the ws line didn't change during sync process
             if !self._ws_is_start() {
                 self.sync = true;
+                self.sync_failures = 0;
             } else {
                 self.driver.disable();
             }
@@ -891,3 +2804,224 @@ where
         Err(WouldBlock)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockI2sPeripheral, Register, ScriptedWsPin};
+
+    // each test using a mock peripheral must use its own slot: 0 is used by driver tests and 1
+    // to 8 by the tests below
+    #[test]
+    fn test_slave_receive_sync() {
+        const RXNE: u32 = 1;
+        // Philips frames start on WS low: wait for the right channel, then confirm it
+        let levels = [false, false, false, true, true, true];
+        let peripheral = MockI2sPeripheral::<1, _>::with_ws_pin(0, ScriptedWsPin::new(&levels));
+        let registers = peripheral.registers();
+        let mut transfer = I2sTransferConfig::new_slave()
+            .receive()
+            .i2s_transfer(peripheral);
+        // sync_confirm_polls is 2, so each not ready attempt reads WS once
+        for _ in 0..3 {
+            assert_eq!(transfer.read::<(i16, i16)>(), Err(WouldBlock));
+            assert_eq!(registers.read(Register::I2scfgr) & 1 << 10, 0);
+        }
+        // ready, then confirmed: the interface is activated
+        assert_eq!(transfer.read::<(i16, i16)>(), Err(WouldBlock));
+        assert_ne!(registers.read(Register::I2scfgr) & 1 << 10, 0);
+        assert_eq!(transfer.driver.ws_pin().reads(), 5);
+
+        registers.write(Register::Sr, RXNE);
+        registers.write(Register::Dr, 0x1234);
+        assert_eq!(transfer.read::<(i16, i16)>(), Err(WouldBlock));
+        registers.write(Register::Dr, 0xfedc);
+        assert_eq!(
+            transfer.read::<(i16, i16)>(),
+            Ok((0x1234, 0xfedcu16 as i16))
+        );
+        assert_eq!(transfer.frame_counter(), 1);
+    }
+
+    /// Underrun a synchronised slave transmit transfer after writing `frame`, and return the
+    /// word written to the data register when it resynchronises.
+    fn reprimed_word<const SLOT: usize>(policy: UnderflowPolicy, frame: (i16, i16)) -> u32 {
+        const TXE: u32 = 1 << 1;
+        const UDR: u32 = 1 << 3;
+        // WS stays high: Philips frames start on WS low, so sync is always ready
+        let peripheral = MockI2sPeripheral::<SLOT, _>::with_ws_pin(0, ScriptedWsPin::new(&[true]));
+        let registers = peripheral.registers();
+        let mut transfer = I2sTransferConfig::new_slave()
+            .underflow_policy(policy)
+            .i2s_transfer(peripheral);
+        // synchronise, then complete the staged frame
+        assert_eq!(transfer.write(frame), Ok(()));
+        registers.write(Register::Sr, TXE);
+        assert_eq!(transfer.write(frame), Err(WouldBlock));
+        assert_eq!(transfer.write(frame), Ok(()));
+        assert_eq!(registers.read(Register::Dr), frame.0 as u16 as u32);
+        registers.write(Register::Sr, UDR);
+        assert_eq!(
+            transfer.write_checked(frame),
+            Err(nb::Error::Other(I2sTransferError::Underrun))
+        );
+        registers.write(Register::Dr, 0xffff);
+        assert_eq!(transfer.write(frame), Ok(()));
+        registers.read(Register::Dr)
+    }
+
+    #[test]
+    fn test_underflow_policy() {
+        assert_eq!(UnderflowPolicy::default(), UnderflowPolicy::HoldLast);
+        assert_eq!(
+            reprimed_word::<2>(UnderflowPolicy::HoldLast, (0x1234, 0x5678)),
+            0x1234
+        );
+        assert_eq!(
+            reprimed_word::<3>(UnderflowPolicy::Zero, (0x1234, 0x5678)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_sync_failures_reset_on_sync() {
+        const RXNE: u32 = 1;
+        const FRE: u32 = 1 << 8;
+        // PCM frames start on WS high: each attempt reads WS low, then low to confirm or high to
+        // fail. The attempts fail, succeed, fail and succeed again.
+        let levels = [false, true, false, false, false, true, false, false];
+        let peripheral = MockI2sPeripheral::<4, _>::with_ws_pin(0, ScriptedWsPin::new(&levels));
+        let registers = peripheral.registers();
+        let mut transfer = I2sTransferConfig::new_slave()
+            .receive()
+            .standard(PcmShortSync)
+            .max_sync_attempts(2)
+            .i2s_transfer(peripheral);
+        // each synchronisation receives a frame, then a frame error drops it
+        registers.write(Register::Sr, RXNE | FRE);
+        registers.write(Register::Dr, 0x1234);
+        let mut frames = 0;
+        transfer.read_while(|frame: i16| {
+            assert_eq!(frame, 0x1234);
+            frames += 1;
+            frames < 2
+        });
+        assert_eq!(frames, 2);
+        assert!(!transfer.sync_failed());
+        assert_eq!(transfer.driver.ws_pin().reads(), levels.len());
+    }
+
+    #[test]
+    fn test_current_frame_channel() {
+        const RXNE: u32 = 1;
+        const CHSIDE: u32 = 1 << 2;
+        let peripheral = MockI2sPeripheral::<5>::new(12_288_000);
+        let registers = peripheral.registers();
+        let mut transfer = I2sTransferConfig::new_master()
+            .receive()
+            .i2s_transfer(peripheral);
+        assert_eq!(transfer.current_frame_channel(), Channel::Left);
+        registers.write(Register::Sr, RXNE);
+        assert_eq!(transfer.read::<(i16, i16)>(), Err(WouldBlock));
+        assert_eq!(transfer.current_frame_channel(), Channel::Left);
+        registers.write(Register::Sr, RXNE | CHSIDE);
+        assert!(transfer.read::<(i16, i16)>().is_ok());
+        assert_eq!(transfer.current_frame_channel(), Channel::Right);
+        // the flag doesn't toggle, so the check fails on the second word
+        registers.write(Register::Sr, RXNE);
+        assert!(!transfer._chside_alternates(1));
+        assert_eq!(transfer.current_frame_channel(), Channel::Left);
+        registers.write(Register::Sr, RXNE | CHSIDE);
+        let mut channel = None;
+        let result = transfer.read_while_with_channel(|_: (i16, i16), c| {
+            channel = Some(c);
+            false
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(channel, Some(Channel::Right));
+    }
+
+    #[test]
+    fn test_preload() {
+        const TXE: u32 = 1 << 1;
+        let peripheral = MockI2sPeripheral::<6>::new(12_288_000);
+        let registers = peripheral.registers();
+        let mut transfer = I2sTransferConfig::new_master().i2s_transfer(peripheral);
+        registers.write(Register::Sr, 0);
+        assert_eq!(transfer.preload((0x1234i16, 0x5678i16)), Err(WouldBlock));
+        registers.write(Register::Sr, TXE);
+        assert_eq!(transfer.preload((0x1234i16, 0x5678i16)), Ok(()));
+        assert_eq!(registers.read(Register::Dr), 0x1234);
+        assert_eq!(registers.read(Register::I2scfgr) & 1 << 10, 0);
+        // the staged frame is counted once it starts going out
+        assert_eq!(transfer.frame_counter(), 0);
+        assert_eq!(transfer.write((0i16, 0i16)), Err(WouldBlock));
+        assert_eq!(registers.read(Register::Dr), 0x5678);
+        assert_eq!(transfer.frame_counter(), 1);
+    }
+
+    static RESYNCS: core::sync::atomic::AtomicU32 = core::sync::atomic::AtomicU32::new(0);
+
+    fn count_resync() {
+        RESYNCS.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[test]
+    fn test_slave_resync() {
+        const RXNE: u32 = 1;
+        const FRE: u32 = 1 << 8;
+        // WS stays high: Philips frames start on WS low, so sync is always ready and confirmed
+        let peripheral = MockI2sPeripheral::<7, _>::with_ws_pin(0, ScriptedWsPin::new(&[true]));
+        let registers = peripheral.registers();
+        let mut transfer = I2sTransferConfig::new_slave()
+            .receive()
+            .i2s_transfer(peripheral);
+        transfer.on_resync(count_resync);
+        assert_eq!(transfer.read_checked::<(i16, i16)>(), Err(WouldBlock));
+        assert_ne!(registers.read(Register::I2scfgr) & 1 << 10, 0);
+        registers.write(Register::Sr, RXNE);
+        registers.write(Register::Dr, 0x1111);
+        assert_eq!(transfer.read_checked::<(i16, i16)>(), Err(WouldBlock));
+        // a frame error in the middle of the frame drops it and deactivates the interface
+        registers.write(Register::Sr, FRE);
+        assert!(matches!(
+            transfer.read_checked::<(i16, i16)>(),
+            Err(nb::Error::Other(I2sTransferError::FrameError))
+        ));
+        assert_eq!(registers.read(Register::I2scfgr) & 1 << 10, 0);
+        assert_eq!(RESYNCS.load(core::sync::atomic::Ordering::Relaxed), 1);
+        // the next read synchronises again, then a whole new frame is received
+        registers.write(Register::Sr, RXNE);
+        assert_eq!(transfer.read_checked::<(i16, i16)>(), Err(WouldBlock));
+        assert_ne!(registers.read(Register::I2scfgr) & 1 << 10, 0);
+        registers.write(Register::Dr, 0x2222);
+        assert_eq!(transfer.read_checked::<(i16, i16)>(), Err(WouldBlock));
+        assert_eq!(transfer.read_checked::<(i16, i16)>(), Ok((0x2222, 0x2222)));
+        assert_eq!(transfer.driver.ws_pin().reads(), 4);
+    }
+
+    #[test]
+    fn test_turnaround() {
+        const I2SCFG_MASK: u32 = 0b11 << 8;
+        const MASTER_RX: u32 = 0b11 << 8;
+        const MASTER_TX: u32 = 0b10 << 8;
+        const I2SE: u32 = 1 << 10;
+        let peripheral = MockI2sPeripheral::<8>::new(12_288_000);
+        let registers = peripheral.registers();
+        let mut transfer = I2sTransferConfig::new_master().i2s_transfer(peripheral);
+        // TXE is set and BSY is clear after reset, so nothing blocks
+        assert_eq!(transfer.write((0x1234i16, 0x5678i16)), Ok(()));
+        assert_eq!(registers.read(Register::Dr), 0x1234);
+        // the partial frame is completed with zeros, then receiving starts right away
+        let transfer = transfer.turnaround();
+        assert_eq!(registers.read(Register::Dr), 0);
+        let i2scfgr = registers.read(Register::I2scfgr);
+        assert_eq!(i2scfgr & I2SCFG_MASK, MASTER_RX);
+        assert_ne!(i2scfgr & I2SE, 0);
+        // back to transmit, the interface stays inactive until the next write
+        let _transfer = transfer.turnaround();
+        let i2scfgr = registers.read(Register::I2scfgr);
+        assert_eq!(i2scfgr & I2SCFG_MASK, MASTER_TX);
+        assert_eq!(i2scfgr & I2SE, 0);
+    }
+}